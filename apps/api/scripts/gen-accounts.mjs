@@ -43,10 +43,9 @@ const out = {
   API_PORT: '4000',
   LOG_LEVEL: 'info',
 
-  // Indexer
-  INDEXER_ENABLED: '1',
-  INDEXER_POLL_MS: '2000',
-  INDEXER_START_LEDGER: '0'
+  // Indexer (crates/eas-indexer)
+  INDEXER_URL: 'http://indexer:4100',
+  INDEXER_POLL_MS: '2000'
 };
 
 writeEnv(out);
@@ -1,7 +1,5 @@
 import { z } from 'zod';
 
-const boolFromString = (v: string) => ['1', 'true', 'yes', 'on'].includes(v.toLowerCase());
-
 const EnvSchema = z.object({
   // Soroban
   SOROBAN_RPC_URL: z.string().url(),
@@ -25,10 +23,9 @@ const EnvSchema = z.object({
   API_PORT: z.coerce.number().int().positive().default(4000),
   LOG_LEVEL: z.string().min(1).default('info'),
 
-  // Indexer
-  INDEXER_ENABLED: z.string().default('1').transform(boolFromString),
-  INDEXER_POLL_MS: z.coerce.number().int().positive().default(2000),
-  INDEXER_START_LEDGER: z.coerce.number().int().nonnegative().default(0)
+  // Indexer (crates/eas-indexer). Chain-derived reads are served from it; the
+  // API's own rows only hold what never goes on-chain (payloads).
+  INDEXER_URL: z.string().url()
 });
 
 export type Env = z.infer<typeof EnvSchema>;
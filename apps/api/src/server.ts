@@ -43,32 +43,48 @@ export function buildServer(env: Env, db: pg.Pool, soroban: EasSoroban) {
 
   app.get('/healthz', async () => ({ ok: true }));
 
-  app.get('/schemas', async () => {
-    const res = await db.query('SELECT * FROM schemas ORDER BY created_ledger DESC');
-    return { schemas: res.rows };
+  // Reads from the Rust indexer, which projects contract events into SQLite.
+  // Its 404s are passed through; a failed request, a non-JSON body or any
+  // other error status means the indexer is down or broken, so we answer 502.
+  async function fromIndexer(path: string): Promise<{ status: number; body: any }> {
+    let res: Response;
+    let body: any;
+    try {
+      res = await fetch(`${env.INDEXER_URL}${path}`);
+      body = await res.json();
+    } catch (e: any) {
+      return { status: 502, body: { error: 'indexer_unavailable', message: String(e?.message ?? e) } };
+    }
+    if (!res.ok && res.status !== 404) {
+      return { status: 502, body: { error: 'indexer_error', status: res.status } };
+    }
+    return { status: res.status, body };
+  }
+
+  app.get('/schemas', async (_req, reply) => {
+    const out = await fromIndexer('/schemas');
+    return reply.code(out.status).send(out.body);
   });
 
   app.get('/schemas/:schemaId', async (req, reply) => {
     const schemaId = (req.params as any).schemaId as string;
-    const res = await db.query('SELECT * FROM schemas WHERE schema_id=$1', [schemaId]);
-    if (res.rowCount === 0) return reply.code(404).send({ error: 'schema_not_found' });
-    return res.rows[0];
+    const out = await fromIndexer(`/schemas/${encodeURIComponent(schemaId)}`);
+    return reply.code(out.status).send(out.body);
   });
 
   app.get('/attestations/:attestationId', async (req, reply) => {
     const attestationId = (req.params as any).attestationId as string;
-    const res = await db.query('SELECT * FROM attestations WHERE attestation_id=$1', [attestationId]);
-    if (res.rowCount === 0) return reply.code(404).send({ error: 'attestation_not_found' });
-    return res.rows[0];
+    const out = await fromIndexer(`/attestations/${encodeURIComponent(attestationId)}`);
+    if (out.status !== 200) return reply.code(out.status).send(out.body);
+    // The payload only exists off-chain, in the row this API stored on POST.
+    const local = await db.query('SELECT payload_json FROM attestations WHERE attestation_id=$1', [attestationId]);
+    return { ...out.body, payload_json: local.rows[0]?.payload_json ?? null };
   });
 
-  app.get('/subjects/:address/attestations', async (req) => {
+  app.get('/subjects/:address/attestations', async (req, reply) => {
     const address = (req.params as any).address as string;
-    const res = await db.query(
-      'SELECT * FROM attestations WHERE subject=$1 ORDER BY timestamp DESC',
-      [address]
-    );
-    return { attestations: res.rows };
+    const out = await fromIndexer(`/subjects/${encodeURIComponent(address)}/attestations`);
+    return reply.code(out.status).send(out.body);
   });
 
   app.post('/schemas', async (req, reply) => {
@@ -143,17 +159,34 @@ export function buildServer(env: Env, db: pg.Pool, soroban: EasSoroban) {
       schemaId: z.string().regex(/^[0-9a-fA-F]{64}$/),
       subject: z.string().min(1),
       payload: z.any(),
+      validFromLedger: z.number().int().positive().nullable().default(null),
       expirationLedger: z.number().int().positive().nullable().default(null)
     });
 
     const body = bodySchema.parse(req.body);
 
-    const dataHashHex = sha256HexFromJson(body.payload);
-
-    // Nonce lives on-chain; fetch and increment.
     const attesterPub = Keypair.fromSecret(env.EAS_ATTESTER_SECRET).publicKey();
     const sourcePub = Keypair.fromSecret(env.EAS_SCHEMA_CREATOR_SECRET).publicKey();
 
+    // Schemas with a field definition hash the payload with the contract's
+    // canonical encoding (hash_data); the others keep the sorted-keys JSON hash.
+    const definition = await soroban.getSchemaDefinition(sourcePub, body.schemaId.toLowerCase());
+    let dataHashHex: string;
+    if (definition === null) {
+      dataHashHex = sha256HexFromJson(body.payload);
+    } else {
+      if (typeof body.payload !== 'object' || body.payload === null || Array.isArray(body.payload)) {
+        return reply.code(400).send({ error: 'payload_must_be_object' });
+      }
+      try {
+        dataHashHex = await soroban.hashData(sourcePub, body.schemaId.toLowerCase(), definition, body.payload);
+      } catch (e: any) {
+        return reply.code(400).send({ error: 'invalid_payload', detail: e.message });
+      }
+    }
+    const encoding = definition === null ? 'json' : 'schema';
+
+    // Nonce lives on-chain; fetch and increment.
     const current = await soroban.getNonce(sourcePub, attesterPub);
     const next = current + 1n;
 
@@ -161,6 +194,7 @@ export function buildServer(env: Env, db: pg.Pool, soroban: EasSoroban) {
       schemaIdHex: body.schemaId.toLowerCase(),
       subject: body.subject,
       dataHashHex,
+      validFromLedger: body.validFromLedger,
       expirationLedger: body.expirationLedger,
       nonce: next
     });
@@ -181,7 +215,7 @@ export function buildServer(env: Env, db: pg.Pool, soroban: EasSoroban) {
       ]
     );
 
-    return { attestationId, dataHash: dataHashHex, nonce: next.toString() };
+    return { attestationId, dataHash: dataHashHex, encoding, nonce: next.toString() };
   });
 
   app.post('/attestations/:attestationId/revoke', async (req, reply) => {
@@ -6,7 +6,8 @@ import {
   Networks,
   TransactionBuilder,
   nativeToScVal,
-  scValToNative
+  scValToNative,
+  xdr
 } from '@stellar/stellar-sdk';
 import { Api, Server } from '@stellar/stellar-sdk/rpc';
 import type { Env } from './env.js';
@@ -17,14 +18,100 @@ export type SchemaFlags = {
   attesterMode: number; // 0=permissionless, 1=issuer_only
 };
 
-export function sha256Hex(input: string) {
-  return crypto.createHash('sha256').update(input).digest('hex');
-}
-
 export function sha256Bytes32(input: string) {
   return crypto.createHash('sha256').update(input).digest();
 }
 
+const CLAIM_TYPES = new Map(Object.entries({
+  bool: 'Bool',
+  u32: 'U32',
+  uint32: 'U32',
+  u64: 'U64',
+  uint64: 'U64',
+  i64: 'I64',
+  int64: 'I64',
+  u128: 'U128',
+  uint128: 'U128',
+  i128: 'I128',
+  int128: 'I128',
+  bytes32: 'Bytes32',
+  bytes: 'Bytes',
+  string: 'String',
+  address: 'Address'
+}));
+
+function claimValue(variant: string, name: string, v: unknown): xdr.ScVal {
+  const invalid = () => new Error(`invalid_value:${name}`);
+  const int = () => {
+    if (typeof v !== 'number' && typeof v !== 'string') throw invalid();
+    try {
+      return BigInt(v);
+    } catch {
+      throw invalid();
+    }
+  };
+  const hex = () => {
+    if (typeof v !== 'string') throw invalid();
+    const s = v.startsWith('0x') ? v.slice(2) : v;
+    if (!/^([0-9a-fA-F]{2})*$/.test(s)) throw invalid();
+    return Buffer.from(s, 'hex');
+  };
+  let inner: xdr.ScVal;
+  switch (variant) {
+    case 'Bool':
+      if (typeof v !== 'boolean') throw invalid();
+      inner = nativeToScVal(v, { type: 'bool' });
+      break;
+    case 'U32':
+      inner = nativeToScVal(Number(int()), { type: 'u32' });
+      break;
+    case 'U64':
+    case 'I64':
+    case 'U128':
+    case 'I128':
+      inner = nativeToScVal(int(), { type: variant.toLowerCase() });
+      break;
+    case 'Bytes32': {
+      const b = hex();
+      if (b.length !== 32) throw invalid();
+      inner = nativeToScVal(b);
+      break;
+    }
+    case 'Bytes':
+      inner = nativeToScVal(hex());
+      break;
+    case 'String':
+      if (typeof v !== 'string') throw invalid();
+      inner = nativeToScVal(v, { type: 'string' });
+      break;
+    default:
+      if (typeof v !== 'string') throw invalid();
+      inner = nativeToScVal(v, { type: 'address' });
+  }
+  return xdr.ScVal.scvVec([xdr.ScVal.scvSymbol(variant), inner]);
+}
+
+// JSON values (keyed by field name) to the contract's `Vec<ClaimValue>`, in
+// definition order. Same rules as `eas-cli encode-data`: integers as numbers
+// or decimal strings, bytes as hex with optional `0x`. The contract checks
+// types and range again when it encodes.
+export function claimValues(definition: string, values: Record<string, unknown>): xdr.ScVal {
+  const fields = definition.split(',').map((f) => {
+    const [type, name, ...rest] = f.trim().split(/\s+/);
+    const variant = CLAIM_TYPES.get(type);
+    if (!variant || !name || rest.length) throw new Error('invalid_schema_definition');
+    return { variant, name };
+  });
+  const extra = Object.keys(values).find((k) => !fields.some((f) => f.name === k));
+  if (extra) throw new Error(`unknown_field:${extra}`);
+  return xdr.ScVal.scvVec(
+    fields.map(({ variant, name }) => {
+      if (!(name in values)) throw new Error(`missing_field:${name}`);
+      return claimValue(variant, name, values[name]);
+    })
+  );
+}
+
 function asContractId(env: Env) {
   return env.SOROBAN_CONTRACT_ID;
 }
@@ -123,6 +210,7 @@ export class EasSoroban {
     schemaIdHex: string;
     subject: string;
     dataHashHex: string;
+    validFromLedger?: number | null;
     expirationLedger: number | null;
     nonce: bigint;
   }) {
@@ -139,6 +227,9 @@ export class EasSoroban {
       nativeToScVal(schemaId),
       nativeToScVal(input.subject, { type: 'address' }),
       nativeToScVal(dataHash),
+      input.validFromLedger == null
+        ? nativeToScVal(null)
+        : nativeToScVal(BigInt(input.validFromLedger), { type: 'u64' }),
       input.expirationLedger === null
         ? nativeToScVal(null)
         : nativeToScVal(BigInt(input.expirationLedger), { type: 'u64' }),
@@ -184,6 +275,22 @@ export class EasSoroban {
     await sendAndPoll(this.rpc, prepared);
   }
 
+  async getSchemaDefinition(sourcePublicKey: string, schemaIdHex: string): Promise<string | null> {
+    const v = await this.simulate(sourcePublicKey, 'get_schema_definition', [
+      nativeToScVal(Buffer.from(schemaIdHex, 'hex'))
+    ]);
+    return v ?? null;
+  }
+
+  // Canonical data_hash computed by the contract itself (`hash_data` view).
+  async hashData(sourcePublicKey: string, schemaIdHex: string, definition: string, values: Record<string, unknown>) {
+    const v = await this.simulate(sourcePublicKey, 'hash_data', [
+      nativeToScVal(Buffer.from(schemaIdHex, 'hex')),
+      claimValues(definition, values)
+    ]);
+    return Buffer.from(v).toString('hex');
+  }
+
   async verify(sourcePublicKey: string, attestationIdHex: string) {
     return this.simulate(sourcePublicKey, 'verify', [nativeToScVal(Buffer.from(attestationIdHex, 'hex'))]);
   }
@@ -1,4 +1,4 @@
-import { describe, expect, it, vi } from 'vitest';
+import { afterEach, describe, expect, it, vi } from 'vitest';
 import crypto from 'node:crypto';
 import { Keypair } from '@stellar/stellar-sdk';
 import { buildServer } from '../src/server.js';
@@ -10,13 +10,24 @@ function hex64(ch: string) {
 function mkEnv(overrides: Partial<any> = {}) {
   return {
     LOG_LEVEL: 'silent',
+    INDEXER_URL: 'http://indexer.test',
     EAS_SCHEMA_CREATOR_SECRET: undefined,
     EAS_ATTESTER_SECRET: undefined,
     ...overrides
   } as any;
 }
 
+function stubIndexer(respond: (url: string) => Promise<Response>) {
+  const fetchMock = vi.fn(async (url: string) => respond(url));
+  vi.stubGlobal('fetch', fetchMock);
+  return fetchMock;
+}
+
 describe('api server', () => {
+  afterEach(() => {
+    vi.unstubAllGlobals();
+  });
+
   it('GET /healthz', async () => {
     const env = mkEnv();
     const db = { query: vi.fn(async () => ({ rows: [], rowCount: 0 })) } as any;
@@ -134,6 +145,7 @@ describe('api server', () => {
     const db = { query: vi.fn(async () => ({ rows: [], rowCount: 0 })) } as any;
 
     const soroban = {
+      getSchemaDefinition: vi.fn(async () => null),
       getNonce: vi.fn(async () => 0n),
       attest: vi.fn(async () => ({ attestationId: hex64('c') }))
     } as any;
@@ -168,6 +180,7 @@ describe('api server', () => {
     expect(j1.attestationId).toBe(hex64('c'));
     expect(j1.nonce).toBe('1');
     expect(j1.dataHash).toMatch(/^[0-9a-f]{64}$/);
+    expect(j1.encoding).toBe('json');
     // stable hashing: same payload with different key ordering
     expect(j2.dataHash).toBe(j1.dataHash);
 
@@ -176,6 +189,55 @@ describe('api server', () => {
     expect(db.query).toHaveBeenCalled();
   });
 
+  it('POST /attestations -> uses the contract encoding when the schema has a definition', async () => {
+    const creator = Keypair.random();
+    const attester = Keypair.random();
+
+    const env = mkEnv({
+      EAS_SCHEMA_CREATOR_SECRET: creator.secret(),
+      EAS_ATTESTER_SECRET: attester.secret()
+    });
+
+    const db = { query: vi.fn(async () => ({ rows: [], rowCount: 0 })) } as any;
+
+    const soroban = {
+      getSchemaDefinition: vi.fn(async () => 'string message'),
+      hashData: vi.fn(async () => hex64('e')),
+      getNonce: vi.fn(async () => 4n),
+      attest: vi.fn(async () => ({ attestationId: hex64('c') }))
+    } as any;
+
+    const app = buildServer(env, db, soroban);
+    await app.ready();
+
+    const schemaId = hex64('d');
+    const post = (payload: unknown) =>
+      app.inject({
+        method: 'POST',
+        url: '/attestations',
+        headers: { 'content-type': 'application/json' },
+        payload: JSON.stringify({ schemaId, subject: attester.publicKey(), payload, expirationLedger: null })
+      });
+
+    const ok = await post({ message: 'oi' });
+    const notObject = await post('oi');
+    await app.close();
+
+    expect(ok.statusCode).toBe(200);
+    expect(ok.json()).toMatchObject({ dataHash: hex64('e'), encoding: 'schema', nonce: '5' });
+    expect(soroban.hashData).toHaveBeenCalledWith(
+      creator.publicKey(),
+      schemaId,
+      'string message',
+      { message: 'oi' }
+    );
+    expect((soroban.attest as any).mock.calls[0][1].dataHashHex).toBe(hex64('e'));
+
+    expect(notObject.statusCode).toBe(400);
+    expect(notObject.json()).toEqual({ error: 'payload_must_be_object' });
+    expect(soroban.attest).toHaveBeenCalledTimes(1);
+  });
+
   it('GET /verify/:attestationId -> jsonSafe encodes bigint and bytes', async () => {
     const creator = Keypair.random();
     const env = mkEnv({ EAS_SCHEMA_CREATOR_SECRET: creator.secret() });
@@ -193,4 +255,53 @@ describe('api server', () => {
     expect(res.statusCode).toBe(200);
     expect(res.json()).toEqual({ result: { amount: '123', raw: '0102ff' } });
   });
+
+  it('GET /attestations/:id -> indexer row plus the stored payload', async () => {
+    const id = hex64('a');
+    const fetchMock = stubIndexer(async () => Response.json({ attestation_id: id, revoked: false }));
+    const db = { query: vi.fn(async () => ({ rows: [{ payload_json: { score: 1 } }], rowCount: 1 })) } as any;
+
+    const app = buildServer(mkEnv(), db, {} as any);
+    await app.ready();
+    const res = await app.inject({ method: 'GET', url: `/attestations/${id}` });
+    await app.close();
+
+    expect(fetchMock).toHaveBeenCalledWith(`http://indexer.test/attestations/${id}`);
+    expect(res.statusCode).toBe(200);
+    expect(res.json()).toEqual({ attestation_id: id, revoked: false, payload_json: { score: 1 } });
+  });
+
+  it('GET /schemas/:id -> passes the indexer 404 through', async () => {
+    stubIndexer(async () => Response.json({ error: 'schema_not_found' }, { status: 404 }));
+    const db = { query: vi.fn() } as any;
+
+    const app = buildServer(mkEnv(), db, {} as any);
+    await app.ready();
+    const res = await app.inject({ method: 'GET', url: `/schemas/${hex64('b')}` });
+    await app.close();
+
+    expect(res.statusCode).toBe(404);
+    expect(res.json()).toEqual({ error: 'schema_not_found' });
+    expect(db.query).not.toHaveBeenCalled();
+  });
+
+  it('GET /schemas -> 502 when the indexer is unreachable or failing', async () => {
+    const cases: Array<() => Promise<Response>> = [
+      async () => {
+        throw new TypeError('fetch failed');
+      },
+      async () => Response.json({ error: 'internal' }, { status: 500 }),
+      async () => new Response('<html>bad gateway</html>', { status: 200 })
+    ];
+    for (const respond of cases) {
+      stubIndexer(respond);
+      const app = buildServer(mkEnv(), { query: vi.fn() } as any, {} as any);
+      await app.ready();
+      const res = await app.inject({ method: 'GET', url: '/schemas' });
+      await app.close();
+
+      expect(res.statusCode).toBe(502);
+      expect(res.json().error).toMatch(/^indexer_/);
+    }
+  });
 });
@@ -406,7 +406,7 @@ export default function App() {
             >
               Atualizar lista
             </button>
-            <div className="small">Dica: a lista vem do Postgres (indexer + inserts do API).</div>
+            <div className="small">Dica: a lista vem do eas-indexer (SQLite) quando INDEXER_URL esta configurado; senao, dos inserts do API no Postgres.</div>
             {schemasList && (
               <div className="small">
                 <pre>{pretty(schemasList)}</pre>
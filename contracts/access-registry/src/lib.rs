@@ -0,0 +1,336 @@
+//! Reference consumer of the EAS contract: members get access by presenting
+//! an attestation under one schema, issued to them by a trusted attester.
+//!
+//! The pattern every product repeats: store the attestation id, not a
+//! "verified" flag, and ask EAS again whenever access matters. Revocation,
+//! expiration and renewal in EAS then apply here immediately, with no
+//! callback into this contract.
+
+#![no_std]
+
+use eas_interface::{EasClient, PolicyFailure, PolicyResult, VerifyPolicy};
+use soroban_sdk::{
+    contract, contracterror, contractevent, contractimpl, contracttype, panic_with_error, Address,
+    BytesN, Env, Vec,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub eas: Address,
+    pub schema_id: BytesN<32>,
+    pub attesters: Vec<Address>,
+}
+
+/// Why `register` refused an attestation: the first failure EAS reported,
+/// in `PolicyFailure` order.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AccessError {
+    NotFound = 1,
+    Revoked = 2,
+    Renounced = 3,
+    Suspended = 4,
+    Expired = 5,
+    NotYetValid = 6,
+    ConsentPending = 7,
+    ConsentRejected = 8,
+    PendingSignatures = 9,
+    SchemaRetired = 10,
+    WrongSchema = 11,
+    UntrustedAttester = 12,
+    TooOld = 13,
+    ExpiresTooSoon = 14,
+    WrongSubject = 15,
+}
+
+impl From<PolicyFailure> for AccessError {
+    fn from(failure: PolicyFailure) -> Self {
+        match failure {
+            PolicyFailure::NotFound => AccessError::NotFound,
+            PolicyFailure::Revoked => AccessError::Revoked,
+            PolicyFailure::Renounced => AccessError::Renounced,
+            PolicyFailure::Suspended => AccessError::Suspended,
+            PolicyFailure::Expired => AccessError::Expired,
+            PolicyFailure::NotYetValid => AccessError::NotYetValid,
+            PolicyFailure::ConsentPending => AccessError::ConsentPending,
+            PolicyFailure::ConsentRejected => AccessError::ConsentRejected,
+            PolicyFailure::PendingSignatures => AccessError::PendingSignatures,
+            PolicyFailure::SchemaRetired => AccessError::SchemaRetired,
+            PolicyFailure::WrongSchema => AccessError::WrongSchema,
+            PolicyFailure::UntrustedAttester => AccessError::UntrustedAttester,
+            PolicyFailure::TooOld => AccessError::TooOld,
+            PolicyFailure::ExpiresTooSoon => AccessError::ExpiresTooSoon,
+            PolicyFailure::WrongSubject => AccessError::WrongSubject,
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    Config,
+    Member(Address),
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccessGranted {
+    #[topic]
+    pub member: Address,
+    pub attestation_id: BytesN<32>,
+}
+
+fn config(env: &Env) -> Config {
+    env.storage().instance().get(&DataKey::Config).unwrap()
+}
+
+// Validity (revocation, expiration, valid_from, consent, cosignatures,
+// schema status) plus our policy, evaluated by EAS in a single call.
+fn evaluate(
+    env: &Env,
+    config: &Config,
+    member: &Address,
+    attestation_id: &BytesN<32>,
+) -> PolicyResult {
+    let policy = VerifyPolicy {
+        schema_id: Some(config.schema_id.clone()),
+        attesters: config.attesters.clone(),
+        max_age: None,
+        min_remaining_validity: None,
+        subject: Some(member.clone()),
+    };
+    EasClient::new(env, &config.eas).verify_with_policy(attestation_id, &policy)
+}
+
+#[contract]
+pub struct AccessRegistry;
+
+#[contractimpl]
+impl AccessRegistry {
+    pub fn __constructor(env: Env, eas: Address, schema_id: BytesN<32>, attesters: Vec<Address>) {
+        if attesters.is_empty() {
+            panic!("no_trusted_attesters");
+        }
+        env.storage().instance().set(
+            &DataKey::Config,
+            &Config {
+                eas,
+                schema_id,
+                attesters,
+            },
+        );
+    }
+
+    // Binds the member to an attestation that passes the policy today.
+    // Registering again replaces the previous attestation (e.g. after a
+    // reissue).
+    pub fn register(env: Env, member: Address, attestation_id: BytesN<32>) {
+        member.require_auth();
+        let result = evaluate(&env, &config(&env), &member, &attestation_id);
+        if let Some(failure) = result.failures.first() {
+            panic_with_error!(&env, AccessError::from(failure));
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Member(member.clone()), &attestation_id);
+        AccessGranted {
+            member,
+            attestation_id,
+        }
+        .publish(&env);
+    }
+
+    // One cross-contract `verify` per call: the answer always reflects the
+    // attestation's current state in EAS.
+    pub fn has_access(env: Env, member: Address) -> bool {
+        let attestation_id: Option<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Member(member.clone()));
+        match attestation_id {
+            Some(id) => evaluate(&env, &config(&env), &member, &id).valid,
+            None => false,
+        }
+    }
+
+    pub fn get_member_attestation(env: Env, member: Address) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::Member(member))
+    }
+
+    pub fn get_config(env: Env) -> Config {
+        config(&env)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use eas_soroban::{EasContract, EasContractClient, SchemaConfig};
+    use soroban_sdk::testutils::{Address as _, EnvTestConfig, Ledger as _};
+    use soroban_sdk::vec;
+
+    fn refused(e: AccessError) -> Option<Result<soroban_sdk::Error, soroban_sdk::InvokeError>> {
+        Some(Ok(e.into()))
+    }
+
+    struct Setup<'a> {
+        env: Env,
+        eas: EasContractClient<'a>,
+        registry: AccessRegistryClient<'a>,
+        schema_id: BytesN<32>,
+        attester: Address,
+    }
+
+    fn setup() -> Setup<'static> {
+        let mut env = Env::default();
+        env.set_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.mock_all_auths();
+        env.ledger().set_sequence_number(10);
+
+        let eas = EasContractClient::new(&env, &env.register(EasContract, ()));
+        let attester = Address::generate(&env);
+        let schema_id = BytesN::from_array(&env, &[1u8; 32]);
+        // revocable, expires_allowed, permissionless, renewable
+        eas.create_schema_with_config(
+            &attester,
+            &schema_id,
+            &true,
+            &true,
+            &0u32,
+            &SchemaConfig {
+                renewable: true,
+                ..Default::default()
+            },
+            &None,
+        );
+
+        let registry_id = env.register(
+            AccessRegistry,
+            (
+                eas.address.clone(),
+                schema_id.clone(),
+                vec![&env, attester.clone()],
+            ),
+        );
+        let registry = AccessRegistryClient::new(&env, &registry_id);
+        Setup {
+            env,
+            eas,
+            registry,
+            schema_id,
+            attester,
+        }
+    }
+
+    impl Setup<'_> {
+        fn attest(
+            &self,
+            attester: &Address,
+            schema_id: &BytesN<32>,
+            subject: &Address,
+            valid_from: Option<u64>,
+            expiration: Option<u64>,
+        ) -> BytesN<32> {
+            let nonce = self.eas.get_nonce(attester) + 1;
+            self.eas.attest(
+                attester,
+                schema_id,
+                subject,
+                &BytesN::from_array(&self.env, &[7u8; 32]),
+                &valid_from,
+                &expiration,
+                &nonce,
+            )
+        }
+    }
+
+    #[test]
+    fn revocation_removes_access() {
+        let s = setup();
+        let member = Address::generate(&s.env);
+        let id = s.attest(&s.attester, &s.schema_id, &member, None, None);
+
+        assert!(!s.registry.has_access(&member));
+        s.registry.register(&member, &id);
+        assert!(s.registry.has_access(&member));
+        assert_eq!(s.registry.get_member_attestation(&member), Some(id.clone()));
+
+        s.eas.revoke_by(&s.attester, &id);
+        assert!(!s.registry.has_access(&member));
+        assert_eq!(
+            s.registry.try_register(&member, &id).err(),
+            refused(AccessError::Revoked)
+        );
+
+        // A fresh attestation restores access.
+        let reissued = s.attest(&s.attester, &s.schema_id, &member, None, None);
+        s.registry.register(&member, &reissued);
+        assert!(s.registry.has_access(&member));
+    }
+
+    #[test]
+    fn expiration_and_renewal_apply_immediately() {
+        let s = setup();
+        let member = Address::generate(&s.env);
+        let id = s.attest(&s.attester, &s.schema_id, &member, None, Some(20));
+        s.registry.register(&member, &id);
+
+        s.env.ledger().set_sequence_number(19);
+        assert!(s.registry.has_access(&member));
+        s.eas.renew(&s.attester, &id, &40u64);
+        s.env.ledger().set_sequence_number(20);
+        assert!(s.registry.has_access(&member));
+        s.env.ledger().set_sequence_number(40);
+        assert!(!s.registry.has_access(&member));
+    }
+
+    #[test]
+    fn rejects_attestations_outside_the_policy() {
+        let s = setup();
+        let member = Address::generate(&s.env);
+
+        let other_schema = BytesN::from_array(&s.env, &[2u8; 32]);
+        s.eas
+            .create_schema(&s.attester, &other_schema, &true, &true, &0u32);
+        let wrong_schema = s.attest(&s.attester, &other_schema, &member, None, None);
+        assert_eq!(
+            s.registry.try_register(&member, &wrong_schema).err(),
+            refused(AccessError::WrongSchema)
+        );
+
+        let stranger = Address::generate(&s.env);
+        let untrusted = s.attest(&stranger, &s.schema_id, &member, None, None);
+        assert_eq!(
+            s.registry.try_register(&member, &untrusted).err(),
+            refused(AccessError::UntrustedAttester)
+        );
+
+        let someone_else = Address::generate(&s.env);
+        let not_theirs = s.attest(&s.attester, &s.schema_id, &someone_else, None, None);
+        assert_eq!(
+            s.registry.try_register(&member, &not_theirs).err(),
+            refused(AccessError::WrongSubject)
+        );
+
+        let deferred = s.attest(&s.attester, &s.schema_id, &member, Some(15), None);
+        assert_eq!(
+            s.registry.try_register(&member, &deferred).err(),
+            refused(AccessError::NotYetValid)
+        );
+        s.env.ledger().set_sequence_number(15);
+        s.registry.register(&member, &deferred);
+        assert!(s.registry.has_access(&member));
+
+        let unknown = BytesN::from_array(&s.env, &[9u8; 32]);
+        assert_eq!(
+            s.registry.try_register(&member, &unknown).err(),
+            refused(AccessError::NotFound)
+        );
+        assert!(!s.registry.has_access(&Address::generate(&s.env)));
+    }
+}
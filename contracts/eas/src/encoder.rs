@@ -0,0 +1,656 @@
+//! Canonical claim encoding for schemas with a field definition, the Soroban
+//! counterpart of EAS's `SchemaEncoder`.
+//!
+//! A definition is a comma-separated list of `<type> <name>` pairs, e.g.
+//! `"u64 score, string name, address issuer"`. Types and their encoding, in
+//! definition order with no separators or field names:
+//!
+//! | type                   | bytes                                  |
+//! |------------------------|----------------------------------------|
+//! | `bool`                 | `0x00` / `0x01`                        |
+//! | `u32` (`uint32`)       | 4, big-endian                          |
+//! | `u64` (`uint64`)       | 8, big-endian                          |
+//! | `i64` (`int64`)        | 8, big-endian two's complement         |
+//! | `u128` (`uint128`)     | 16, big-endian                         |
+//! | `i128` (`int128`)      | 16, big-endian two's complement        |
+//! | `bytes32`              | 32, raw                                |
+//! | `bytes`, `string`      | u32 big-endian length, then the bytes  |
+//! | `address`              | as `string`, of the `G...`/`C...` strkey |
+//!
+//! `data_hash = sha256(encoding)`. The contract side (`encode_data`,
+//! `hash_data`, `attest_with_data`) and the host side (`host`, behind the
+//! `encoder` feature) are both checked against
+//! `testdata/encoder_vectors.json`.
+
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env, String, Vec};
+
+pub const MAX_SCHEMA_FIELDS: usize = 32;
+pub const MAX_SCHEMA_DEFINITION_LEN: usize = 512;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldType {
+    Bool,
+    U32,
+    U64,
+    I64,
+    U128,
+    I128,
+    Bytes32,
+    Bytes,
+    String,
+    Address,
+}
+
+impl FieldType {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "bool" => FieldType::Bool,
+            "u32" | "uint32" => FieldType::U32,
+            "u64" | "uint64" => FieldType::U64,
+            "i64" | "int64" => FieldType::I64,
+            "u128" | "uint128" => FieldType::U128,
+            "i128" | "int128" => FieldType::I128,
+            "bytes32" => FieldType::Bytes32,
+            "bytes" => FieldType::Bytes,
+            "string" => FieldType::String,
+            "address" => FieldType::Address,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SchemaError {
+    Empty,
+    TooLong,
+    TooManyFields,
+    /// Not exactly `<type> <name>`, or the name is not an identifier.
+    InvalidField,
+    UnknownType,
+    DuplicateField,
+}
+
+impl SchemaError {
+    /// Same string the contract panics with.
+    pub fn code(self) -> &'static str {
+        match self {
+            SchemaError::Empty => "empty_schema",
+            SchemaError::TooLong => "schema_definition_too_long",
+            SchemaError::TooManyFields => "too_many_fields",
+            SchemaError::InvalidField => "invalid_field",
+            SchemaError::UnknownType => "unknown_type",
+            SchemaError::DuplicateField => "duplicate_field",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Field<'a> {
+    pub ty: FieldType,
+    pub name: &'a str,
+}
+
+/// Parsed definition; fixed capacity so the contract can use it without an
+/// allocator.
+pub struct SchemaFields<'a> {
+    fields: [Field<'a>; MAX_SCHEMA_FIELDS],
+    len: usize,
+}
+
+impl<'a> SchemaFields<'a> {
+    pub fn fields(&self) -> &[Field<'a>] {
+        &self.fields[..self.len]
+    }
+}
+
+pub fn parse_schema(definition: &str) -> Result<SchemaFields<'_>, SchemaError> {
+    if definition.len() > MAX_SCHEMA_DEFINITION_LEN {
+        return Err(SchemaError::TooLong);
+    }
+    if definition.trim().is_empty() {
+        return Err(SchemaError::Empty);
+    }
+
+    let mut out = SchemaFields {
+        fields: [Field {
+            ty: FieldType::Bool,
+            name: "",
+        }; MAX_SCHEMA_FIELDS],
+        len: 0,
+    };
+    for part in definition.split(',') {
+        let mut tokens = part.split_whitespace();
+        let (Some(ty), Some(name), None) = (tokens.next(), tokens.next(), tokens.next()) else {
+            return Err(SchemaError::InvalidField);
+        };
+        let ty = FieldType::from_name(ty).ok_or(SchemaError::UnknownType)?;
+        if !is_identifier(name) {
+            return Err(SchemaError::InvalidField);
+        }
+        if out.fields().iter().any(|f| f.name == name) {
+            return Err(SchemaError::DuplicateField);
+        }
+        if out.len == MAX_SCHEMA_FIELDS {
+            return Err(SchemaError::TooManyFields);
+        }
+        out.fields[out.len] = Field { ty, name };
+        out.len += 1;
+    }
+    Ok(out)
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// One claim value as passed to the contract, in definition order.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ClaimValue {
+    Bool(bool),
+    U32(u32),
+    U64(u64),
+    I64(i64),
+    U128(u128),
+    I128(i128),
+    Bytes32(BytesN<32>),
+    Bytes(Bytes),
+    String(String),
+    Address(Address),
+}
+
+impl ClaimValue {
+    fn field_type(&self) -> FieldType {
+        match self {
+            ClaimValue::Bool(_) => FieldType::Bool,
+            ClaimValue::U32(_) => FieldType::U32,
+            ClaimValue::U64(_) => FieldType::U64,
+            ClaimValue::I64(_) => FieldType::I64,
+            ClaimValue::U128(_) => FieldType::U128,
+            ClaimValue::I128(_) => FieldType::I128,
+            ClaimValue::Bytes32(_) => FieldType::Bytes32,
+            ClaimValue::Bytes(_) => FieldType::Bytes,
+            ClaimValue::String(_) => FieldType::String,
+            ClaimValue::Address(_) => FieldType::Address,
+        }
+    }
+}
+
+fn fail(e: SchemaError) -> ! {
+    match e {
+        SchemaError::Empty => panic!("empty_schema"),
+        SchemaError::TooLong => panic!("schema_definition_too_long"),
+        SchemaError::TooManyFields => panic!("too_many_fields"),
+        SchemaError::InvalidField => panic!("invalid_field"),
+        SchemaError::UnknownType => panic!("unknown_type"),
+        SchemaError::DuplicateField => panic!("duplicate_field"),
+    }
+}
+
+fn definition_str<'a>(
+    definition: &String,
+    buf: &'a mut [u8; MAX_SCHEMA_DEFINITION_LEN],
+) -> &'a str {
+    let len = definition.len() as usize;
+    if len > MAX_SCHEMA_DEFINITION_LEN {
+        fail(SchemaError::TooLong);
+    }
+    definition.copy_into_slice(&mut buf[..len]);
+    core::str::from_utf8(&buf[..len]).unwrap_or_else(|_| fail(SchemaError::InvalidField))
+}
+
+/// Panics with the `SchemaError` code if `definition` does not parse.
+pub(crate) fn validate_definition(definition: &String) {
+    let mut buf = [0u8; MAX_SCHEMA_DEFINITION_LEN];
+    if let Err(e) = parse_schema(definition_str(definition, &mut buf)) {
+        fail(e);
+    }
+}
+
+pub(crate) fn encode_claim(env: &Env, definition: &String, values: &Vec<ClaimValue>) -> Bytes {
+    let mut buf = [0u8; MAX_SCHEMA_DEFINITION_LEN];
+    let schema = parse_schema(definition_str(definition, &mut buf)).unwrap_or_else(|e| fail(e));
+    let fields = schema.fields();
+    if values.len() as usize != fields.len() {
+        panic!("claim_arity_mismatch");
+    }
+
+    let mut out = Bytes::new(env);
+    for (field, value) in fields.iter().zip(values.iter()) {
+        if field.ty != value.field_type() {
+            panic!("claim_type_mismatch");
+        }
+        match value {
+            ClaimValue::Bool(b) => out.push_back(b as u8),
+            ClaimValue::U32(n) => out.extend_from_array(&n.to_be_bytes()),
+            ClaimValue::U64(n) => out.extend_from_array(&n.to_be_bytes()),
+            ClaimValue::I64(n) => out.extend_from_array(&n.to_be_bytes()),
+            ClaimValue::U128(n) => out.extend_from_array(&n.to_be_bytes()),
+            ClaimValue::I128(n) => out.extend_from_array(&n.to_be_bytes()),
+            ClaimValue::Bytes32(b) => out.extend_from_array(&b.to_array()),
+            ClaimValue::Bytes(b) => put_prefixed(&mut out, &b),
+            ClaimValue::String(s) => put_prefixed(&mut out, &s.to_bytes()),
+            ClaimValue::Address(a) => put_prefixed(&mut out, &a.to_string().to_bytes()),
+        }
+    }
+    out
+}
+
+fn put_prefixed(out: &mut Bytes, b: &Bytes) {
+    out.extend_from_array(&b.len().to_be_bytes());
+    out.append(b);
+}
+
+/// Host-side encoder, for clients that compute `data_hash` before calling
+/// `attest` (or that need the preimage of a salted commitment).
+#[cfg(feature = "encoder")]
+pub mod host {
+    extern crate std;
+
+    use std::format;
+    use std::string::{String as StdString, ToString};
+    use std::vec::Vec as StdVec;
+
+    use serde_json::{Map, Value as Json};
+    use sha2::{Digest, Sha256};
+    use stellar_strkey::Strkey;
+
+    use super::{parse_schema, FieldType, SchemaError};
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum Value {
+        Bool(bool),
+        U32(u32),
+        U64(u64),
+        I64(i64),
+        U128(u128),
+        I128(i128),
+        Bytes32([u8; 32]),
+        Bytes(StdVec<u8>),
+        String(StdString),
+        /// `G...` or `C...` strkey.
+        Address(StdString),
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum EncodeError {
+        Schema(SchemaError),
+        Arity {
+            expected: usize,
+            found: usize,
+        },
+        MissingField(StdString),
+        UnknownField(StdString),
+        /// Wrong type for the field, or a malformed value (bad hex, strkey,
+        /// out-of-range integer).
+        InvalidValue(StdString),
+    }
+
+    impl core::fmt::Display for EncodeError {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            match self {
+                EncodeError::Schema(e) => write!(f, "schema: {}", e.code()),
+                EncodeError::Arity { expected, found } => {
+                    write!(f, "expected {expected} values, got {found}")
+                }
+                EncodeError::MissingField(name) => write!(f, "missing field `{name}`"),
+                EncodeError::UnknownField(name) => write!(f, "unknown field `{name}`"),
+                EncodeError::InvalidValue(name) => write!(f, "invalid value for `{name}`"),
+            }
+        }
+    }
+
+    impl std::error::Error for EncodeError {}
+
+    fn ty(value: &Value) -> FieldType {
+        match value {
+            Value::Bool(_) => FieldType::Bool,
+            Value::U32(_) => FieldType::U32,
+            Value::U64(_) => FieldType::U64,
+            Value::I64(_) => FieldType::I64,
+            Value::U128(_) => FieldType::U128,
+            Value::I128(_) => FieldType::I128,
+            Value::Bytes32(_) => FieldType::Bytes32,
+            Value::Bytes(_) => FieldType::Bytes,
+            Value::String(_) => FieldType::String,
+            Value::Address(_) => FieldType::Address,
+        }
+    }
+
+    /// `values` in definition order.
+    pub fn encode(definition: &str, values: &[Value]) -> Result<StdVec<u8>, EncodeError> {
+        let schema = parse_schema(definition).map_err(EncodeError::Schema)?;
+        let fields = schema.fields();
+        if values.len() != fields.len() {
+            return Err(EncodeError::Arity {
+                expected: fields.len(),
+                found: values.len(),
+            });
+        }
+
+        let mut out = StdVec::new();
+        for (field, value) in fields.iter().zip(values) {
+            if field.ty != ty(value) {
+                return Err(EncodeError::InvalidValue(field.name.to_string()));
+            }
+            match value {
+                Value::Bool(b) => out.push(*b as u8),
+                Value::U32(n) => out.extend_from_slice(&n.to_be_bytes()),
+                Value::U64(n) => out.extend_from_slice(&n.to_be_bytes()),
+                Value::I64(n) => out.extend_from_slice(&n.to_be_bytes()),
+                Value::U128(n) => out.extend_from_slice(&n.to_be_bytes()),
+                Value::I128(n) => out.extend_from_slice(&n.to_be_bytes()),
+                Value::Bytes32(b) => out.extend_from_slice(b),
+                Value::Bytes(b) => put_prefixed(&mut out, b),
+                Value::String(s) => put_prefixed(&mut out, s.as_bytes()),
+                Value::Address(a) => {
+                    match Strkey::from_string(a) {
+                        Ok(Strkey::PublicKeyEd25519(_)) | Ok(Strkey::Contract(_)) => {}
+                        _ => return Err(EncodeError::InvalidValue(field.name.to_string())),
+                    }
+                    put_prefixed(&mut out, a.as_bytes())
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// JSON object keyed by field name. Integers may be numbers or decimal
+    /// strings (use strings beyond 2^53), bytes are hex with optional `0x`.
+    pub fn encode_json(
+        definition: &str,
+        values: &Map<StdString, Json>,
+    ) -> Result<StdVec<u8>, EncodeError> {
+        let schema = parse_schema(definition).map_err(EncodeError::Schema)?;
+        let fields = schema.fields();
+        if let Some(extra) = values
+            .keys()
+            .find(|k| !fields.iter().any(|f| f.name == k.as_str()))
+        {
+            return Err(EncodeError::UnknownField(extra.clone()));
+        }
+
+        let mut typed = StdVec::with_capacity(fields.len());
+        for field in fields {
+            let v = values
+                .get(field.name)
+                .ok_or_else(|| EncodeError::MissingField(field.name.to_string()))?;
+            let invalid = || EncodeError::InvalidValue(field.name.to_string());
+            typed.push(match field.ty {
+                FieldType::Bool => Value::Bool(v.as_bool().ok_or_else(invalid)?),
+                FieldType::U32 => Value::U32(int(v).ok_or_else(invalid)?),
+                FieldType::U64 => Value::U64(int(v).ok_or_else(invalid)?),
+                FieldType::I64 => Value::I64(int(v).ok_or_else(invalid)?),
+                FieldType::U128 => Value::U128(int(v).ok_or_else(invalid)?),
+                FieldType::I128 => Value::I128(int(v).ok_or_else(invalid)?),
+                FieldType::Bytes32 => Value::Bytes32(
+                    hex_bytes(v)
+                        .and_then(|b| b.try_into().ok())
+                        .ok_or_else(invalid)?,
+                ),
+                FieldType::Bytes => Value::Bytes(hex_bytes(v).ok_or_else(invalid)?),
+                FieldType::String => Value::String(v.as_str().ok_or_else(invalid)?.to_string()),
+                FieldType::Address => Value::Address(v.as_str().ok_or_else(invalid)?.to_string()),
+            });
+        }
+        encode(definition, &typed)
+    }
+
+    pub fn data_hash(encoded: &[u8]) -> [u8; 32] {
+        Sha256::digest(encoded).into()
+    }
+
+    fn int<T: core::str::FromStr>(v: &Json) -> Option<T> {
+        match v {
+            Json::Number(n) => format!("{n}").parse().ok(),
+            Json::String(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn hex_bytes(v: &Json) -> Option<StdVec<u8>> {
+        let s = v.as_str()?;
+        hex::decode(s.strip_prefix("0x").unwrap_or(s)).ok()
+    }
+
+    fn put_prefixed(out: &mut StdVec<u8>, b: &[u8]) {
+        out.extend_from_slice(&(b.len() as u32).to_be_bytes());
+        out.extend_from_slice(b);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use serde_json::Value as Json;
+    use soroban_sdk::testutils::{Address as _, EnvTestConfig};
+
+    use super::*;
+    use crate::{EasContract, EasContractClient, SchemaConfig};
+
+    const VECTORS: &str = include_str!("../testdata/encoder_vectors.json");
+
+    fn vectors() -> Json {
+        serde_json::from_str(VECTORS).unwrap()
+    }
+
+    fn claim_values(env: &Env, definition: &str, values: &Json) -> Vec<ClaimValue> {
+        let schema = parse_schema(definition).unwrap();
+        let mut out = Vec::new(env);
+        for field in schema.fields() {
+            let v = &values[field.name];
+            let int = || v.as_str().map_or_else(|| v.to_string(), |s| s.to_string());
+            let hex = |s: &str| {
+                let mut b = std::vec![0u8; s.len() / 2];
+                for (i, byte) in b.iter_mut().enumerate() {
+                    *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).unwrap();
+                }
+                b
+            };
+            out.push_back(match field.ty {
+                FieldType::Bool => ClaimValue::Bool(v.as_bool().unwrap()),
+                FieldType::U32 => ClaimValue::U32(int().parse().unwrap()),
+                FieldType::U64 => ClaimValue::U64(int().parse().unwrap()),
+                FieldType::I64 => ClaimValue::I64(int().parse().unwrap()),
+                FieldType::U128 => ClaimValue::U128(int().parse().unwrap()),
+                FieldType::I128 => ClaimValue::I128(int().parse().unwrap()),
+                FieldType::Bytes32 => ClaimValue::Bytes32(BytesN::from_array(
+                    env,
+                    &hex(v.as_str().unwrap()).try_into().unwrap(),
+                )),
+                FieldType::Bytes => {
+                    ClaimValue::Bytes(Bytes::from_slice(env, &hex(v.as_str().unwrap())))
+                }
+                FieldType::String => ClaimValue::String(String::from_str(env, v.as_str().unwrap())),
+                FieldType::Address => {
+                    ClaimValue::Address(Address::from_str(env, v.as_str().unwrap()))
+                }
+            });
+        }
+        out
+    }
+
+    fn to_hex(b: &[u8]) -> std::string::String {
+        b.iter().map(|x| std::format!("{x:02x}")).collect()
+    }
+
+    #[test]
+    fn parses_definitions() {
+        let schema = parse_schema(" uint64 score,string  name , address issuer").unwrap();
+        assert_eq!(
+            schema.fields(),
+            &[
+                Field {
+                    ty: FieldType::U64,
+                    name: "score"
+                },
+                Field {
+                    ty: FieldType::String,
+                    name: "name"
+                },
+                Field {
+                    ty: FieldType::Address,
+                    name: "issuer"
+                },
+            ]
+        );
+
+        for case in vectors()["invalid"].as_array().unwrap() {
+            let err = parse_schema(case["schema"].as_str().unwrap())
+                .err()
+                .unwrap();
+            assert_eq!(err.code(), case["error"].as_str().unwrap(), "{case}");
+        }
+
+        let mut long = std::string::String::new();
+        for i in 0..=MAX_SCHEMA_FIELDS {
+            long.push_str(&std::format!("bool f{i},"));
+        }
+        long.pop();
+        assert_eq!(parse_schema(&long).err(), Some(SchemaError::TooManyFields));
+        let long = std::format!("string {}", "x".repeat(MAX_SCHEMA_DEFINITION_LEN));
+        assert_eq!(parse_schema(&long).err(), Some(SchemaError::TooLong));
+    }
+
+    fn setup() -> (Env, EasContractClient<'static>) {
+        let mut env = Env::default();
+        env.set_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.mock_all_auths();
+        let client = EasContractClient::new(&env, &env.register(EasContract, ()));
+        (env, client)
+    }
+
+    #[test]
+    fn contract_encoding_matches_vectors() {
+        let (env, client) = setup();
+        let creator = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        for (i, v) in vectors()["vectors"].as_array().unwrap().iter().enumerate() {
+            let definition = v["schema"].as_str().unwrap();
+            let schema_id = BytesN::from_array(&env, &[i as u8 + 1; 32]);
+            client.create_schema(&creator, &schema_id, &true, &false, &0u32);
+            client.set_schema_definition(&creator, &schema_id, &String::from_str(&env, definition));
+
+            let values = claim_values(&env, definition, &v["values"]);
+            let encoded = client.encode_data(&schema_id, &values);
+            let mut buf = std::vec![0u8; encoded.len() as usize];
+            encoded.copy_into_slice(&mut buf);
+            assert_eq!(to_hex(&buf), v["encoded"].as_str().unwrap());
+
+            let id = client.attest_with_data(
+                &creator,
+                &schema_id,
+                &subject,
+                &values,
+                &None,
+                &None,
+                &(i as u64 + 1),
+            );
+            let data_hash = client.get_attestation(&id).data_hash;
+            assert_eq!(
+                to_hex(&data_hash.to_array()),
+                v["data_hash"].as_str().unwrap()
+            );
+            assert_eq!(client.hash_data(&schema_id, &values), data_hash);
+        }
+    }
+
+    #[test]
+    fn contract_rejects_mismatched_claims() {
+        let (env, client) = setup();
+        let creator = Address::generate(&env);
+        let schema_id = BytesN::from_array(&env, &[9u8; 32]);
+        client.create_schema(&creator, &schema_id, &true, &false, &0u32);
+
+        let values = soroban_sdk::vec![&env, ClaimValue::U64(1), ClaimValue::Bool(true)];
+        assert!(client.try_hash_data(&schema_id, &values).is_err()); // no definition yet
+        assert!(client
+            .try_set_schema_definition(
+                &creator,
+                &schema_id,
+                &String::from_str(&env, "u64 a, float b")
+            )
+            .is_err());
+
+        let definition = String::from_str(&env, "u64 a, bool b");
+        client.set_schema_definition(&creator, &schema_id, &definition);
+        assert_eq!(
+            client.get_schema_definition(&schema_id),
+            Some(definition.clone())
+        );
+        assert!(client
+            .try_set_schema_definition(&creator, &schema_id, &definition)
+            .is_err());
+
+        client.hash_data(&schema_id, &values);
+        let swapped = soroban_sdk::vec![&env, ClaimValue::Bool(true), ClaimValue::U64(1)];
+        assert!(client.try_hash_data(&schema_id, &swapped).is_err());
+        let short = soroban_sdk::vec![&env, ClaimValue::U64(1)];
+        assert!(client.try_hash_data(&schema_id, &short).is_err());
+
+        // Claims of commitment schemas must stay off-chain.
+        let salted_id = BytesN::from_array(&env, &[10u8; 32]);
+        client.create_schema_with_config(
+            &creator,
+            &salted_id,
+            &true,
+            &false,
+            &0u32,
+            &SchemaConfig {
+                salted_commitments: true,
+                ..Default::default()
+            },
+            &None,
+        );
+        client.set_schema_definition(&creator, &salted_id, &definition);
+        let subject = Address::generate(&env);
+        assert!(client
+            .try_attest_with_data(&creator, &salted_id, &subject, &values, &None, &None, &1u64)
+            .is_err());
+    }
+
+    #[cfg(feature = "encoder")]
+    #[test]
+    fn host_encoding_matches_vectors() {
+        for v in vectors()["vectors"].as_array().unwrap() {
+            let definition = v["schema"].as_str().unwrap();
+            let encoded = host::encode_json(definition, v["values"].as_object().unwrap()).unwrap();
+            assert_eq!(to_hex(&encoded), v["encoded"].as_str().unwrap());
+            assert_eq!(
+                to_hex(&host::data_hash(&encoded)),
+                v["data_hash"].as_str().unwrap()
+            );
+        }
+
+        let def = "u32 level, address issuer";
+        let mut values: serde_json::Map<_, _> =
+            serde_json::from_str(r#"{"level": 4294967296, "issuer": "GABC"}"#).unwrap();
+        assert_eq!(
+            host::encode_json(def, &values),
+            Err(host::EncodeError::InvalidValue("level".into()))
+        );
+        values.insert("level".into(), 1.into());
+        assert_eq!(
+            host::encode_json(def, &values),
+            Err(host::EncodeError::InvalidValue("issuer".into()))
+        );
+        values.remove("issuer");
+        assert_eq!(
+            host::encode_json(def, &values),
+            Err(host::EncodeError::MissingField("issuer".into()))
+        );
+        values.insert("extra".into(), true.into());
+        assert_eq!(
+            host::encode_json(def, &values),
+            Err(host::EncodeError::UnknownField("extra".into()))
+        );
+    }
+}
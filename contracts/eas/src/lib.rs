@@ -1,61 +1,236 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Symbol};
+use soroban_sdk::{
+    contract, contracterror, contractevent, contractimpl, contracttype, panic_with_error, token,
+    xdr::ToXdr, Address, Bytes, BytesN, Env, Event, FromVal, IntoVal, String, Symbol, Val, Vec,
+};
+
+use eas_interface::{
+    CONSENT_ACCEPTED, CONSENT_NOT_REQUIRED, CONSENT_PENDING, CONSENT_REJECTED, MODE_ISSUER_ONLY,
+    MODE_PERMISSIONLESS, STATUS_ACTIVE, STATUS_DEPRECATED, STATUS_RETIRED,
+};
+
+pub use eas_interface::{
+    Attestation, BatchAttestation, BatchLeaf, HistoricalStatus, PolicyFailure, PolicyResult,
+    Renewal, Schema, StatusChange, StatusKind, TimestampRecord, VerifyPolicy, VerifyResult,
+};
+pub use encoder::ClaimValue;
+
+// Rejections of the schema's validity policy, typed so attesters can tell an
+// expiration in the past from one outside the allowed period.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ValidityError {
+    ExpirationRequired = 1,
+    ExpirationInPast = 2,
+    ValidityTooShort = 3,
+    ValidityTooLong = 4,
+}
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct Schema {
-    pub schema_uri_hash: BytesN<32>,
-    pub creator: Address,
-    pub revocable: bool,
-    pub expires_allowed: bool,
-    pub attester_mode: u32, // 0=permissionless, 1=issuer_only
+pub struct FeeConfig {
+    pub token: Address, // SEP-41 token contract
+    pub amount: i128,
+    pub recipient: Address,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct Attestation {
-    pub schema_id: BytesN<32>,
-    pub attester: Address,
-    pub subject: Address,
-    pub data_hash: BytesN<32>,
-    pub timestamp: u64,          // ledger sequence
-    pub expiration: Option<u64>, // ledger sequence
-    pub revoked: bool,
+pub struct SchemaVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct VerifyResult {
-    pub exists: bool,
-    pub valid: bool,
-    pub revoked: bool,
-    pub expired: bool,
-    pub schema_id: BytesN<32>,
-    pub attester: Address,
-    pub subject: Address,
-    pub data_hash: BytesN<32>,
-    pub timestamp: u64,
-    pub expiration: Option<u64>,
+pub struct SchemaMetadata {
+    pub name: String,
+    pub description: String, // free text or URI
+    pub version: SchemaVersion,
+    pub tags: Vec<Symbol>,
+}
+
+// Schema flags fixed at creation (see `create_schema_with_config`).
+// `create_schema` uses the defaults.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SchemaConfig {
+    pub renewable: bool,
+    // `data_hash` is `sha256(salt || claim)`, see `verify_claim`. Fixed at
+    // creation because it changes what existing data hashes mean.
+    pub salted_commitments: bool,
+    // `data_hash` is the Merkle root of the claim fields, see `verify_field`.
+    // Fixed for the same reason, and exclusive with `salted_commitments`.
+    pub merkle_claims: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MultisigConfig {
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+}
+
+// Head of the event hash chain: `seq` state changes have been announced and
+// `hash` folds every event published for them (see `chain_event`).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateCheckpoint {
+    pub seq: u64,
+    pub hash: BytesN<32>,
+    pub ledger: u64,
 }
 
 #[contracttype]
 #[derive(Clone)]
 enum DataKey {
     Schema(BytesN<32>),
+    SchemaMetadata(BytesN<32>),
+    SchemaDefinition(BytesN<32>),
+    SchemaFee(BytesN<32>),
+    SchemaMultisig(BytesN<32>),
+    SchemaRetiredAt(BytesN<32>),
     Attestation(BytesN<32>),
+    Renewals(BytesN<32>),
+    StatusHistory(BytesN<32>),
+    Signers(BytesN<32>),
+    Batch(BytesN<32>),
+    BatchRevocations(BytesN<32>, u32), // (root_id, chunk)
     Nonce(Address),
+    FeePayer(Address),
+    Timestamp(BytesN<32>),
+    OffchainRevocation(Address, BytesN<32>), // (signer, hash)
+    StatusList(Address, u32),                // (attester, chunk)
+    StatusListLen(Address),
+    StatusRevokedAt(Address, u32), // (attester, index)
     NextAttId,
+    NextBatchId,
+    EventSeq,
+    StateHash,
 }
 
-const MODE_PERMISSIONLESS: u32 = 0;
-const MODE_ISSUER_ONLY: u32 = 1;
+const MAX_METADATA_NAME_LEN: u32 = 64;
+const MAX_METADATA_DESCRIPTION_LEN: u32 = 256;
+const MAX_METADATA_TAGS: u32 = 8;
+
+const MAX_MULTISIG_SIGNERS: u32 = 20;
+
+const MAX_MERKLE_PROOF_LEN: u32 = 32;
+const MERKLE_LEAF_TAG: u8 = 0x00;
+const MERKLE_NODE_TAG: u8 = 0x01;
+
+const MAX_BATCH_SIZE: u32 = 1 << 20;
+const BATCH_ID_TAG: &[u8] = b"eas-soroban:batch";
+// Revocation bitmaps are split into persistent entries of this many bits.
+const BITMAP_CHUNK_BITS: u32 = 8192;
 
 fn now_ledger(env: &Env) -> u64 {
     env.ledger().sequence().into()
 }
 
+fn next_event_seq(env: &Env) -> u64 {
+    let seq: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::EventSeq)
+        .unwrap_or(0u64)
+        .saturating_add(1);
+    env.storage().instance().set(&DataKey::EventSeq, &seq);
+    seq
+}
+
+// state_hash = sha256(prev_hash || xdr(ScVal::Vec([topics, data]))), starting
+// from 32 zero bytes, so consumers can replay it from getEvents output.
+fn chain_event(env: &Env, topics: Vec<Val>, data: Val) {
+    let prev: BytesN<32> = env
+        .storage()
+        .instance()
+        .get(&DataKey::StateHash)
+        .unwrap_or_else(|| BytesN::from_array(env, &[0u8; 32]));
+    let mut buf = Bytes::from_array(env, &prev.to_array());
+    buf.append(&(topics, data).to_xdr(env));
+    let hash: BytesN<32> = env.crypto().sha256(&buf).into();
+    env.storage().instance().set(&DataKey::StateHash, &hash);
+}
+
+// Legacy tuple events keep their single `(name,)` topic and carry the
+// sequence number as an extra last data element, so the positional fields the
+// current indexer reads are unchanged.
+fn publish_legacy<D: IntoVal<Env, Val>>(env: &Env, name: &str, data: D) {
+    publish_legacy_at(env, name, next_event_seq(env), data);
+}
+
 #[allow(deprecated)]
+fn publish_legacy_at<D: IntoVal<Env, Val>>(env: &Env, name: &str, seq: u64, data: D) {
+    let topics = (Symbol::new(env, name),);
+    let mut data: Vec<Val> = Vec::from_val(env, &data.into_val(env));
+    data.push_back(seq.into_val(env));
+    env.events().publish(topics.clone(), data.clone());
+    chain_event(env, topics.into_val(env), data.to_val());
+}
+
+// v2 events carry the sequence number in their `seq` data field. When an
+// operation also publishes a legacy tuple, both share one sequence number.
+fn publish_event<E: Event>(env: &Env, event: &E) {
+    event.publish(env);
+    chain_event(env, event.topics(env), event.data(env));
+}
+
+// v2 events: the fixed topic is the snake_case struct name, followed by the
+// #[topic] fields, so RPC getEvents can filter by schema, attester or subject.
+// The legacy tuples are still published while the `legacy-events` feature is
+// on (the default) so indexers can move over before it is switched off.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SchemaCreated {
+    #[topic]
+    pub schema_id: BytesN<32>,
+    #[topic]
+    pub creator: Address,
+    pub schema_uri_hash: BytesN<32>,
+    pub revocable: bool,
+    pub expires_allowed: bool,
+    pub attester_mode: u32,
+    pub ledger: u64,
+    pub seq: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attested {
+    #[topic]
+    pub schema_id: BytesN<32>,
+    #[topic]
+    pub attester: Address,
+    #[topic]
+    pub subject: Address,
+    pub attestation_id: BytesN<32>,
+    pub data_hash: BytesN<32>,
+    pub timestamp: u64,
+    pub expiration: Option<u64>,
+    /// Bit in the attester's status list, for `StatusListRevoked`.
+    pub status_index: Option<u32>,
+    pub seq: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Revoked {
+    #[topic]
+    pub schema_id: BytesN<32>,
+    #[topic]
+    pub attester: Address,
+    #[topic]
+    pub subject: Address,
+    pub attestation_id: BytesN<32>,
+    pub revoker: Address,
+    pub ledger: u64,
+    pub seq: u64,
+}
+
 fn emit_schema_created(
     env: &Env,
     schema_id: &BytesN<32>,
@@ -65,9 +240,13 @@ fn emit_schema_created(
     expires_allowed: bool,
     attester_mode: u32,
 ) {
+    let seq = next_event_seq(env);
     // Kept in legacy format for compatibility with our current off-chain indexer.
-    env.events().publish(
-        (Symbol::new(env, "SchemaCreated"),),
+    #[cfg(feature = "legacy-events")]
+    publish_legacy_at(
+        env,
+        "SchemaCreated",
+        seq,
         (
             schema_id.clone(),
             creator.clone(),
@@ -78,46 +257,354 @@ fn emit_schema_created(
             now_ledger(env),
         ),
     );
+
+    publish_event(
+        env,
+        &SchemaCreated {
+            schema_id: schema_id.clone(),
+            creator: creator.clone(),
+            schema_uri_hash: schema_uri_hash.clone(),
+            revocable,
+            expires_allowed,
+            attester_mode,
+            ledger: now_ledger(env),
+            seq,
+        },
+    );
 }
 
-#[allow(deprecated)]
-#[allow(clippy::too_many_arguments)]
-fn emit_attested(
+fn emit_attested(env: &Env, attestation_id: &BytesN<32>, att: &Attestation) {
+    let seq = next_event_seq(env);
+    // Kept in legacy format for compatibility with our current off-chain indexer.
+    #[cfg(feature = "legacy-events")]
+    publish_legacy_at(
+        env,
+        "Attested",
+        seq,
+        (
+            attestation_id.clone(),
+            att.schema_id.clone(),
+            att.attester.clone(),
+            att.subject.clone(),
+            att.data_hash.clone(),
+            att.timestamp,
+            att.expiration,
+        ),
+    );
+
+    publish_event(
+        env,
+        &Attested {
+            schema_id: att.schema_id.clone(),
+            attester: att.attester.clone(),
+            subject: att.subject.clone(),
+            attestation_id: attestation_id.clone(),
+            data_hash: att.data_hash.clone(),
+            timestamp: att.timestamp,
+            expiration: att.expiration,
+            status_index: att.status_index,
+            seq,
+        },
+    );
+}
+
+fn emit_revoked(env: &Env, attestation_id: &BytesN<32>, att: &Attestation, revoker: &Address) {
+    let seq = next_event_seq(env);
+    // Kept in legacy format for compatibility with our current off-chain indexer.
+    #[cfg(feature = "legacy-events")]
+    publish_legacy_at(
+        env,
+        "Revoked",
+        seq,
+        (attestation_id.clone(), revoker.clone(), now_ledger(env)),
+    );
+
+    publish_event(
+        env,
+        &Revoked {
+            schema_id: att.schema_id.clone(),
+            attester: att.attester.clone(),
+            subject: att.subject.clone(),
+            attestation_id: attestation_id.clone(),
+            revoker: revoker.clone(),
+            ledger: now_ledger(env),
+            seq,
+        },
+    );
+}
+
+fn emit_renewed(
     env: &Env,
     attestation_id: &BytesN<32>,
-    schema_id: &BytesN<32>,
     attester: &Address,
+    previous_expiration: u64,
+    new_expiration: u64,
+) {
+    publish_legacy(
+        env,
+        "Renewed",
+        (
+            attestation_id.clone(),
+            attester.clone(),
+            previous_expiration,
+            new_expiration,
+            now_ledger(env),
+        ),
+    );
+}
+
+fn emit_validity_policy_updated(env: &Env, schema_id: &BytesN<32>, schema: &Schema) {
+    publish_legacy(
+        env,
+        "ValidityPolicyUpdated",
+        (
+            schema_id.clone(),
+            schema.min_validity,
+            schema.max_validity,
+            schema.expiration_required,
+            now_ledger(env),
+        ),
+    );
+}
+
+fn emit_schema_status_changed(env: &Env, schema_id: &BytesN<32>, schema: &Schema) {
+    publish_legacy(
+        env,
+        "SchemaStatusChanged",
+        (
+            schema_id.clone(),
+            schema.status,
+            schema.successor_schema_id.clone(),
+            now_ledger(env),
+        ),
+    );
+}
+
+fn emit_schema_metadata_updated(
+    env: &Env,
+    schema_id: &BytesN<32>,
+    metadata: &Option<SchemaMetadata>,
+) {
+    publish_legacy(
+        env,
+        "SchemaMetadataUpdated",
+        (schema_id.clone(), metadata.clone(), now_ledger(env)),
+    );
+}
+
+fn emit_schema_definition_set(env: &Env, schema_id: &BytesN<32>, definition: &String) {
+    publish_legacy(
+        env,
+        "SchemaDefinitionSet",
+        (schema_id.clone(), definition.clone(), now_ledger(env)),
+    );
+}
+
+fn emit_schema_fee_updated(env: &Env, schema_id: &BytesN<32>, fee: &Option<FeeConfig>) {
+    publish_legacy(
+        env,
+        "SchemaFeeUpdated",
+        (schema_id.clone(), fee.clone(), now_ledger(env)),
+    );
+}
+
+fn emit_fee_payer_updated(env: &Env, attester: &Address, payer: &Option<Address>) {
+    publish_legacy(
+        env,
+        "FeePayerUpdated",
+        (attester.clone(), payer.clone(), now_ledger(env)),
+    );
+}
+
+fn emit_fee_paid(env: &Env, attestation_id: &BytesN<32>, payer: &Address, fee: &FeeConfig) {
+    publish_legacy(
+        env,
+        "FeePaid",
+        (
+            attestation_id.clone(),
+            payer.clone(),
+            fee.token.clone(),
+            fee.recipient.clone(),
+            fee.amount,
+            now_ledger(env),
+        ),
+    );
+}
+
+fn emit_schema_flag_updated(env: &Env, schema_id: &BytesN<32>, flag: &str, value: bool) {
+    publish_legacy(
+        env,
+        "SchemaFlagUpdated",
+        (
+            schema_id.clone(),
+            Symbol::new(env, flag),
+            value,
+            now_ledger(env),
+        ),
+    );
+}
+
+fn emit_renounced(env: &Env, attestation_id: &BytesN<32>, subject: &Address) {
+    publish_legacy(
+        env,
+        "Renounced",
+        (attestation_id.clone(), subject.clone(), now_ledger(env)),
+    );
+}
+
+fn emit_consent(env: &Env, name: &str, attestation_id: &BytesN<32>, subject: &Address) {
+    publish_legacy(
+        env,
+        name,
+        (attestation_id.clone(), subject.clone(), now_ledger(env)),
+    );
+}
+
+fn emit_suspension(env: &Env, name: &str, attestation_id: &BytesN<32>, attester: &Address) {
+    publish_legacy(
+        env,
+        name,
+        (attestation_id.clone(), attester.clone(), now_ledger(env)),
+    );
+}
+
+fn emit_schema_multisig_updated(
+    env: &Env,
+    schema_id: &BytesN<32>,
+    config: &Option<MultisigConfig>,
+) {
+    publish_legacy(
+        env,
+        "SchemaMultisigUpdated",
+        (schema_id.clone(), config.clone(), now_ledger(env)),
+    );
+}
+
+fn emit_attestation_proposed(
+    env: &Env,
+    attestation_id: &BytesN<32>,
+    schema_id: &BytesN<32>,
+    proposer: &Address,
     subject: &Address,
     data_hash: &BytesN<32>,
-    timestamp: u64,
-    expiration: Option<u64>,
 ) {
-    // Kept in legacy format for compatibility with our current off-chain indexer.
-    env.events().publish(
-        (Symbol::new(env, "Attested"),),
+    publish_legacy(
+        env,
+        "AttestationProposed",
         (
             attestation_id.clone(),
             schema_id.clone(),
-            attester.clone(),
+            proposer.clone(),
             subject.clone(),
             data_hash.clone(),
-            timestamp,
-            expiration,
+            now_ledger(env),
         ),
     );
 }
 
-#[allow(deprecated)]
-fn emit_revoked(env: &Env, attestation_id: &BytesN<32>, revoker: &Address) {
-    // Kept in legacy format for compatibility with our current off-chain indexer.
-    env.events().publish(
-        (Symbol::new(env, "Revoked"),),
-        (attestation_id.clone(), revoker.clone(), now_ledger(env)),
+fn emit_proposal_cancelled(env: &Env, attestation_id: &BytesN<32>, proposer: &Address) {
+    publish_legacy(
+        env,
+        "ProposalCancelled",
+        (attestation_id.clone(), proposer.clone(), now_ledger(env)),
+    );
+}
+
+fn emit_cosigned(env: &Env, attestation_id: &BytesN<32>, signer: &Address, signatures: u32) {
+    publish_legacy(
+        env,
+        "Cosigned",
+        (
+            attestation_id.clone(),
+            signer.clone(),
+            signatures,
+            now_ledger(env),
+        ),
+    );
+}
+
+fn emit_status_list_revoked(env: &Env, attester: &Address, indices: &Vec<u32>) {
+    publish_legacy(
+        env,
+        "StatusListRevoked",
+        (attester.clone(), indices.clone(), now_ledger(env)),
+    );
+}
+
+fn emit_timestamped(env: &Env, data: &BytesN<32>, record: &TimestampRecord) {
+    publish_legacy(
+        env,
+        "Timestamped",
+        (data.clone(), record.ledger, record.time),
+    );
+}
+
+fn emit_revoked_offchain(env: &Env, signer: &Address, data: &BytesN<32>, record: &TimestampRecord) {
+    publish_legacy(
+        env,
+        "RevokedOffchain",
+        (signer.clone(), data.clone(), record.ledger, record.time),
+    );
+}
+
+fn emit_batch_attested(env: &Env, root_id: &BytesN<32>, batch: &BatchAttestation) {
+    publish_legacy(
+        env,
+        "BatchAttested",
+        (
+            root_id.clone(),
+            batch.schema_id.clone(),
+            batch.attester.clone(),
+            batch.root.clone(),
+            batch.count,
+            batch.timestamp,
+            batch.expiration,
+        ),
+    );
+}
+
+fn emit_batch_revoked(env: &Env, root_id: &BytesN<32>, revoker: &Address) {
+    publish_legacy(
+        env,
+        "BatchRevoked",
+        (root_id.clone(), revoker.clone(), now_ledger(env)),
+    );
+}
+
+fn emit_batch_leaves_revoked(
+    env: &Env,
+    root_id: &BytesN<32>,
+    revoker: &Address,
+    indices: &Vec<u32>,
+) {
+    publish_legacy(
+        env,
+        "BatchLeavesRevoked",
+        (
+            root_id.clone(),
+            revoker.clone(),
+            indices.clone(),
+            now_ledger(env),
+        ),
     );
 }
 
 fn next_attestation_id(env: &Env) -> BytesN<32> {
-    let k = DataKey::NextAttId;
+    next_id(env, DataKey::NextAttId)
+}
+
+// Batch ids are hashed under their own tag, so they never share the counter
+// layout of attestation ids: sha256("eas-soroban:batch" || counter id).
+fn next_batch_id(env: &Env) -> BytesN<32> {
+    let mut msg = Bytes::from_slice(env, BATCH_ID_TAG);
+    msg.append(&Bytes::from_array(
+        env,
+        &next_id(env, DataKey::NextBatchId).to_array(),
+    ));
+    env.crypto().sha256(&msg).into()
+}
+
+fn next_id(env: &Env, k: DataKey) -> BytesN<32> {
     let mut n: u64 = env.storage().instance().get(&k).unwrap_or(0);
     n = n.saturating_add(1);
     env.storage().instance().set(&k, &n);
@@ -135,39 +622,541 @@ fn require_schema_exists(env: &Env, schema_id: &BytesN<32>) -> Schema {
         .unwrap_or_else(|| panic!("schema_not_found"))
 }
 
-fn require_attestation_exists(env: &Env, attestation_id: &BytesN<32>) -> Attestation {
-    env.storage()
+fn validate_metadata(metadata: &SchemaMetadata) {
+    if metadata.name.is_empty() || metadata.name.len() > MAX_METADATA_NAME_LEN {
+        panic!("invalid_metadata_name");
+    }
+    if metadata.description.len() > MAX_METADATA_DESCRIPTION_LEN {
+        panic!("metadata_description_too_long");
+    }
+    if metadata.tags.len() > MAX_METADATA_TAGS {
+        panic!("too_many_metadata_tags");
+    }
+}
+
+fn store_schema_metadata(env: &Env, schema_id: &BytesN<32>, metadata: &Option<SchemaMetadata>) {
+    let key = DataKey::SchemaMetadata(schema_id.clone());
+    match metadata {
+        Some(m) => {
+            validate_metadata(m);
+            env.storage().persistent().set(&key, m);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+// Transfers the schema fee (if any) from the attester's designated payer, or
+// from the attester itself. Returns the payer that was charged.
+// `units` is the number of attestations being paid for; the returned config
+// carries the total amount charged.
+fn charge_attestation_fee(
+    env: &Env,
+    schema_id: &BytesN<32>,
+    attester: &Address,
+    units: u32,
+) -> Option<(Address, FeeConfig)> {
+    let mut fee: FeeConfig = env
+        .storage()
         .persistent()
-        .get(&DataKey::Attestation(attestation_id.clone()))
-        .unwrap_or_else(|| panic!("attestation_not_found"))
+        .get(&DataKey::SchemaFee(schema_id.clone()))?;
+    let payer: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::FeePayer(attester.clone()))
+        .unwrap_or_else(|| attester.clone());
+    if payer != *attester {
+        payer.require_auth();
+    }
+
+    fee.amount = fee
+        .amount
+        .checked_mul(units.into())
+        .unwrap_or_else(|| panic!("fee_overflow"));
+    token::Client::new(env, &fee.token).transfer(&payer, &fee.recipient, &fee.amount);
+    Some((payer, fee))
 }
 
-#[contract]
-pub struct EasContract;
+fn set_consent(env: &Env, subject: &Address, attestation_id: &BytesN<32>, consent_status: u32) {
+    subject.require_auth();
+    let mut att = require_attestation_exists(env, attestation_id);
 
-#[contractimpl]
-impl EasContract {
-    // RF.C.01
-    pub fn create_schema(
-        env: Env,
-        creator: Address,
-        schema_uri_hash: BytesN<32>,
-        revocable: bool,
-        expires_allowed: bool,
-        attester_mode: u32,
-    ) -> BytesN<32> {
-        creator.require_auth();
+    if *subject != att.subject {
+        panic!("not_subject");
+    }
+    if att.consent_status != CONSENT_PENDING {
+        panic!("consent_not_pending");
+    }
 
-        if attester_mode != MODE_PERMISSIONLESS && attester_mode != MODE_ISSUER_ONLY {
-            panic!("invalid_attester_mode");
-        }
+    att.consent_status = consent_status;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Attestation(attestation_id.clone()), &att);
+}
 
-        // MVP: schema_id == schema_uri_hash
-        let schema_id = schema_uri_hash.clone();
-        let key = DataKey::Schema(schema_id.clone());
-        if env.storage().persistent().has(&key) {
-            panic!("schema_already_exists");
-        }
+// Claim-field leaf: sha256(0x00 || field). The prefix keeps leaves and inner
+// nodes (0x01) apart, so a node's preimage can never pass as a field.
+fn merkle_field_leaf(env: &Env, field: &Bytes) -> BytesN<32> {
+    let mut msg = Bytes::from_array(env, &[MERKLE_LEAF_TAG]);
+    msg.append(field);
+    env.crypto().sha256(&msg).into()
+}
+
+// Sorted-pair sha256 Merkle tree (same convention as OpenZeppelin's
+// MerkleProof), so proofs do not need to carry left/right positions.
+// Nodes are sha256(0x01 || min || max).
+fn merkle_root_from_proof(env: &Env, leaf: &BytesN<32>, proof: &Vec<BytesN<32>>) -> BytesN<32> {
+    if proof.len() > MAX_MERKLE_PROOF_LEN {
+        panic!("merkle_proof_too_long");
+    }
+
+    let mut node = leaf.clone();
+    for sibling in proof.iter() {
+        let (a, b) = if node.to_array() <= sibling.to_array() {
+            (node, sibling)
+        } else {
+            (sibling, node)
+        };
+        let mut msg = Bytes::from_array(env, &[MERKLE_NODE_TAG]);
+        msg.append(&Bytes::from_array(env, &a.to_array()));
+        msg.append(&Bytes::from_array(env, &b.to_array()));
+        node = env.crypto().sha256(&msg).into();
+    }
+    node
+}
+
+// Positional counterpart of `merkle_root_from_proof` for batches: bit i of `index`
+// tells whether the node is the right child at level i. Binding the leaf to
+// its index is what makes per-leaf revocation bits meaningful. Nodes are
+// sha256(0x01 || left || right), leaves are tagged 0x00 as in the claim tree.
+fn merkle_root_from_indexed_proof(
+    env: &Env,
+    leaf: &BytesN<32>,
+    index: u32,
+    proof: &Vec<BytesN<32>>,
+) -> BytesN<32> {
+    if proof.len() > MAX_MERKLE_PROOF_LEN {
+        panic!("merkle_proof_too_long");
+    }
+
+    let mut node = leaf.clone();
+    let mut idx = index;
+    for sibling in proof.iter() {
+        let (a, b) = if idx & 1 == 0 {
+            (node, sibling)
+        } else {
+            (sibling, node)
+        };
+        let mut msg = Bytes::from_array(env, &[MERKLE_NODE_TAG]);
+        msg.append(&Bytes::from_array(env, &a.to_array()));
+        msg.append(&Bytes::from_array(env, &b.to_array()));
+        node = env.crypto().sha256(&msg).into();
+        idx >>= 1;
+    }
+    if idx != 0 {
+        // Index does not fit in a tree of this depth.
+        panic!("invalid_leaf_index");
+    }
+    node
+}
+
+// leaf = sha256(0x00 || index_be || subject_xdr || data_hash)
+fn batch_leaf_hash(env: &Env, leaf: &BatchLeaf) -> BytesN<32> {
+    let mut msg = Bytes::from_array(env, &[MERKLE_LEAF_TAG]);
+    msg.append(&Bytes::from_array(env, &leaf.index.to_be_bytes()));
+    msg.append(&leaf.subject.clone().to_xdr(env));
+    msg.append(&Bytes::from_array(env, &leaf.data_hash.to_array()));
+    env.crypto().sha256(&msg).into()
+}
+
+// Bits are numbered most-significant first within each byte, as in W3C
+// Status List 2021. Chunks grow lazily up to the highest bit set.
+fn bitmap_set<F: Fn(u32) -> DataKey>(env: &Env, chunk_key: F, indices: &Vec<u32>) {
+    let mut current: Option<(u32, Bytes)> = None;
+    for index in indices.iter() {
+        let chunk = index / BITMAP_CHUNK_BITS;
+        let offset = index % BITMAP_CHUNK_BITS;
+
+        if current.as_ref().map(|(c, _)| *c) != Some(chunk) {
+            if let Some((c, bytes)) = current.take() {
+                env.storage().persistent().set(&chunk_key(c), &bytes);
+            }
+            let bytes = env
+                .storage()
+                .persistent()
+                .get(&chunk_key(chunk))
+                .unwrap_or_else(|| Bytes::new(env));
+            current = Some((chunk, bytes));
+        }
+
+        if let Some((_, bytes)) = current.as_mut() {
+            let byte_idx = offset / 8;
+            while bytes.len() <= byte_idx {
+                bytes.push_back(0);
+            }
+            let b = bytes.get_unchecked(byte_idx);
+            bytes.set(byte_idx, b | (0x80 >> (offset % 8)));
+        }
+    }
+    if let Some((c, bytes)) = current {
+        env.storage().persistent().set(&chunk_key(c), &bytes);
+    }
+}
+
+fn bitmap_get<F: Fn(u32) -> DataKey>(env: &Env, chunk_key: F, index: u32) -> bool {
+    let bytes: Option<Bytes> = env
+        .storage()
+        .persistent()
+        .get(&chunk_key(index / BITMAP_CHUNK_BITS));
+    let offset = index % BITMAP_CHUNK_BITS;
+    match bytes.and_then(|b| b.get(offset / 8)) {
+        Some(b) => b & (0x80 >> (offset % 8)) != 0,
+        None => false,
+    }
+}
+
+fn next_status_index(env: &Env, attester: &Address) -> u32 {
+    let k = DataKey::StatusListLen(attester.clone());
+    let n: u32 = env.storage().persistent().get(&k).unwrap_or(0);
+    env.storage().persistent().set(&k, &(n + 1));
+    n
+}
+
+// Revoked either individually (`revoke_by`) or through the attester's status list.
+fn is_revoked(env: &Env, att: &Attestation) -> bool {
+    att.revoked
+        || att.status_index.is_some_and(|index| {
+            bitmap_get(
+                env,
+                |chunk| DataKey::StatusList(att.attester.clone(), chunk),
+                index,
+            )
+        })
+}
+
+fn record_status(env: &Env, attestation_id: &BytesN<32>, kind: StatusKind) {
+    let key = DataKey::StatusHistory(attestation_id.clone());
+    let mut history: Vec<StatusChange> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env));
+    history.push_back(StatusChange {
+        kind,
+        ledger: now_ledger(env),
+    });
+    env.storage().persistent().set(&key, &history);
+}
+
+// Stored history plus the status-list revocation, whose ledger is kept per
+// bit (`StatusRevokedAt`) because bulk revocation does not know the ids.
+fn status_history(env: &Env, attestation_id: &BytesN<32>, att: &Attestation) -> Vec<StatusChange> {
+    let mut history: Vec<StatusChange> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::StatusHistory(attestation_id.clone()))
+        .unwrap_or_else(|| Vec::new(env));
+
+    let bulk: Option<u64> = att.status_index.and_then(|index| {
+        env.storage()
+            .persistent()
+            .get(&DataKey::StatusRevokedAt(att.attester.clone(), index))
+    });
+    if let Some(ledger) = bulk {
+        let already_revoked = history
+            .iter()
+            .any(|c| c.kind == StatusKind::Revoked && c.ledger <= ledger);
+        if !already_revoked {
+            let pos = history
+                .iter()
+                .position(|c| c.ledger > ledger)
+                .map_or(history.len(), |p| p as u32);
+            history.insert(
+                pos,
+                StatusChange {
+                    kind: StatusKind::Revoked,
+                    ledger,
+                },
+            );
+        }
+    }
+    history
+}
+
+// Suspension follows the revocation rules but can be undone.
+fn set_suspended(env: &Env, attester: &Address, attestation_id: &BytesN<32>, suspended: bool) {
+    attester.require_auth();
+    let mut att = require_attestation_exists(env, attestation_id);
+
+    if *attester != att.attester {
+        panic!("not_attester");
+    }
+
+    let schema = require_schema_exists(env, &att.schema_id);
+    if !schema.revocable {
+        panic!("not_revocable");
+    }
+    if is_revoked(env, &att) {
+        panic!("attestation_revoked");
+    }
+
+    if att.suspended == suspended {
+        return;
+    }
+
+    att.suspended = suspended;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Attestation(attestation_id.clone()), &att);
+
+    if suspended {
+        record_status(env, attestation_id, StatusKind::Suspended);
+        // Event: Suspended(attestation_id, attester, timestamp)
+        emit_suspension(env, "Suspended", attestation_id, attester);
+    } else {
+        record_status(env, attestation_id, StatusKind::Reinstated);
+        // Event: Reinstated(attestation_id, attester, timestamp)
+        emit_suspension(env, "Reinstated", attestation_id, attester);
+    }
+}
+
+fn now_record(env: &Env) -> TimestampRecord {
+    TimestampRecord {
+        ledger: now_ledger(env),
+        time: env.ledger().timestamp(),
+    }
+}
+
+// Keeps the first record for `data`; later calls are no-ops.
+fn record_timestamp(env: &Env, data: &BytesN<32>) -> TimestampRecord {
+    let key = DataKey::Timestamp(data.clone());
+    if let Some(existing) = env.storage().persistent().get(&key) {
+        return existing;
+    }
+
+    let record = now_record(env);
+    env.storage().persistent().set(&key, &record);
+
+    // Event: Timestamped(data, ledger, time)
+    emit_timestamped(env, data, &record);
+    record
+}
+
+fn require_batch_exists(env: &Env, root_id: &BytesN<32>) -> BatchAttestation {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Batch(root_id.clone()))
+        .unwrap_or_else(|| panic!("batch_not_found"))
+}
+
+fn require_schema_owner(schema: &Schema, owner: &Address) {
+    owner.require_auth();
+    if *owner != schema.creator {
+        panic!("not_schema_owner");
+    }
+}
+
+// Enforces the schema's validity period limits. The period is counted from the
+// later of the current ledger and `valid_from`.
+fn check_validity_period(
+    env: &Env,
+    schema: &Schema,
+    valid_from: Option<u64>,
+    expiration: Option<u64>,
+) {
+    let exp = match expiration {
+        Some(exp) => exp,
+        None => {
+            if schema.expiration_required {
+                panic_with_error!(env, ValidityError::ExpirationRequired);
+            }
+            return;
+        }
+    };
+
+    // `verify` treats `now >= exp` as expired, so `exp == now` is already dead.
+    let now = now_ledger(env);
+    if exp <= now {
+        panic_with_error!(env, ValidityError::ExpirationInPast);
+    }
+
+    let start = valid_from.map_or(now, |from| from.max(now));
+    let period = exp.saturating_sub(start);
+    if let Some(min) = schema.min_validity {
+        if period < min {
+            panic_with_error!(env, ValidityError::ValidityTooShort);
+        }
+    }
+    if let Some(max) = schema.max_validity {
+        if period > max {
+            panic_with_error!(env, ValidityError::ValidityTooLong);
+        }
+    }
+}
+
+fn require_attestation_exists(env: &Env, attestation_id: &BytesN<32>) -> Attestation {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Attestation(attestation_id.clone()))
+        .unwrap_or_else(|| panic!("attestation_not_found"))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn issue_attestation(
+    env: &Env,
+    attester: &Address,
+    schema_id: &BytesN<32>,
+    subject: &Address,
+    data_hash: &BytesN<32>,
+    valid_from: Option<u64>,
+    expiration: Option<u64>,
+    nonce: u64,
+    pending_signatures: bool,
+) -> BytesN<32> {
+    let schema = require_schema_exists(env, schema_id);
+
+    match schema.status {
+        STATUS_DEPRECATED => panic!("schema_deprecated"),
+        STATUS_RETIRED => panic!("schema_retired"),
+        _ => {}
+    }
+
+    if expiration.is_some() && !schema.expires_allowed {
+        panic!("expiration_not_allowed");
+    }
+
+    // An attestation must be valid for at least one ledger.
+    if let (Some(from), Some(exp)) = (valid_from, expiration) {
+        if from >= exp {
+            panic!("invalid_validity_window");
+        }
+    }
+    check_validity_period(env, &schema, valid_from, expiration);
+
+    if schema.attester_mode == MODE_ISSUER_ONLY && *attester != schema.creator {
+        panic!("issuer_only");
+    }
+
+    // Monotonic nonce per attester.
+    let nonce_key = DataKey::Nonce(attester.clone());
+    let current_nonce: u64 = env.storage().persistent().get(&nonce_key).unwrap_or(0);
+    if nonce != current_nonce.saturating_add(1) {
+        panic!("bad_nonce");
+    }
+    env.storage().persistent().set(&nonce_key, &nonce);
+
+    let fee_paid = charge_attestation_fee(env, schema_id, attester, 1);
+
+    let attestation_id = next_attestation_id(env);
+    let key = DataKey::Attestation(attestation_id.clone());
+
+    let timestamp = now_ledger(env);
+    let attestation = Attestation {
+        schema_id: schema_id.clone(),
+        attester: attester.clone(),
+        subject: subject.clone(),
+        data_hash: data_hash.clone(),
+        timestamp,
+        valid_from,
+        expiration,
+        revoked: false,
+        pending_signatures,
+        consent_status: if schema.requires_subject_consent {
+            CONSENT_PENDING
+        } else {
+            CONSENT_NOT_REQUIRED
+        },
+        renounced: false,
+        // Only revocable attestations get a bit, so bulk revocation can never
+        // touch attestations under non-revocable schemas.
+        status_index: if schema.revocable {
+            Some(next_status_index(env, attester))
+        } else {
+            None
+        },
+        suspended: false,
+    };
+    env.storage().persistent().set(&key, &attestation);
+
+    if !pending_signatures {
+        record_status(env, &attestation_id, StatusKind::Issued);
+
+        // Event: Attested(attestation_id, schema_id, attester, subject, data_hash, timestamp, expiration)
+        emit_attested(env, &attestation_id, &attestation);
+    }
+
+    if let Some((payer, fee)) = fee_paid {
+        // Event: FeePaid(attestation_id, payer, token, recipient, amount, timestamp)
+        emit_fee_paid(env, &attestation_id, &payer, &fee);
+    }
+
+    attestation_id
+}
+
+// Issues a threshold attestation once enough signers have approved it.
+fn complete_proposal(env: &Env, attestation_id: &BytesN<32>, att: &mut Attestation) {
+    att.pending_signatures = false;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Attestation(attestation_id.clone()), att);
+    record_status(env, attestation_id, StatusKind::Issued);
+
+    // Event: Attested(attestation_id, schema_id, attester, subject, data_hash, timestamp, expiration)
+    emit_attested(env, attestation_id, att);
+}
+
+#[contract]
+pub struct EasContract;
+
+#[contractimpl]
+impl EasContract {
+    // RF.C.01
+    pub fn create_schema(
+        env: Env,
+        creator: Address,
+        schema_uri_hash: BytesN<32>,
+        revocable: bool,
+        expires_allowed: bool,
+        attester_mode: u32,
+    ) -> BytesN<32> {
+        Self::create_schema_with_config(
+            env,
+            creator,
+            schema_uri_hash,
+            revocable,
+            expires_allowed,
+            attester_mode,
+            SchemaConfig::default(),
+            None,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_schema_with_config(
+        env: Env,
+        creator: Address,
+        schema_uri_hash: BytesN<32>,
+        revocable: bool,
+        expires_allowed: bool,
+        attester_mode: u32,
+        config: SchemaConfig,
+        metadata: Option<SchemaMetadata>,
+    ) -> BytesN<32> {
+        creator.require_auth();
+
+        if attester_mode != MODE_PERMISSIONLESS && attester_mode != MODE_ISSUER_ONLY {
+            panic!("invalid_attester_mode");
+        }
+
+        if config.salted_commitments && config.merkle_claims {
+            panic!("conflicting_commitment_modes");
+        }
+
+        // MVP: schema_id == schema_uri_hash
+        let schema_id = schema_uri_hash.clone();
+        let key = DataKey::Schema(schema_id.clone());
+        if env.storage().persistent().has(&key) {
+            panic!("schema_already_exists");
+        }
 
         let schema = Schema {
             schema_uri_hash: schema_uri_hash.clone(),
@@ -175,6 +1164,16 @@ impl EasContract {
             revocable,
             expires_allowed,
             attester_mode,
+            renewable: config.renewable,
+            min_validity: None,
+            max_validity: None,
+            expiration_required: false,
+            status: STATUS_ACTIVE,
+            successor_schema_id: None,
+            requires_subject_consent: false,
+            subject_can_renounce: false,
+            salted_commitments: config.salted_commitments,
+            merkle_claims: config.merkle_claims,
         };
         env.storage().persistent().set(&key, &schema);
 
@@ -189,171 +1188,2681 @@ impl EasContract {
             schema.attester_mode,
         );
 
-        schema_id
-    }
+        if metadata.is_some() {
+            store_schema_metadata(&env, &schema_id, &metadata);
+            // Event: SchemaMetadataUpdated(schema_id, metadata, timestamp)
+            emit_schema_metadata_updated(&env, &schema_id, &metadata);
+        }
+
+        schema_id
+    }
+
+    pub fn update_schema_metadata(
+        env: Env,
+        owner: Address,
+        schema_id: BytesN<32>,
+        metadata: Option<SchemaMetadata>,
+    ) {
+        let schema = require_schema_exists(&env, &schema_id);
+        require_schema_owner(&schema, &owner);
+
+        store_schema_metadata(&env, &schema_id, &metadata);
+
+        // Event: SchemaMetadataUpdated(schema_id, metadata, timestamp)
+        emit_schema_metadata_updated(&env, &schema_id, &metadata);
+    }
+
+    pub fn get_schema_metadata(env: Env, schema_id: BytesN<32>) -> Option<SchemaMetadata> {
+        require_schema_exists(&env, &schema_id);
+        env.storage()
+            .persistent()
+            .get(&DataKey::SchemaMetadata(schema_id))
+    }
+
+    // Field layout of the claims, e.g. "u64 score, string name, address
+    // issuer" (see `encoder`). Write-once: existing data hashes depend on it.
+    pub fn set_schema_definition(
+        env: Env,
+        owner: Address,
+        schema_id: BytesN<32>,
+        definition: String,
+    ) {
+        let schema = require_schema_exists(&env, &schema_id);
+        require_schema_owner(&schema, &owner);
+
+        let key = DataKey::SchemaDefinition(schema_id.clone());
+        if env.storage().persistent().has(&key) {
+            panic!("schema_definition_already_set");
+        }
+        encoder::validate_definition(&definition);
+        env.storage().persistent().set(&key, &definition);
+
+        // Event: SchemaDefinitionSet(schema_id, definition, timestamp)
+        emit_schema_definition_set(&env, &schema_id, &definition);
+    }
+
+    pub fn get_schema_definition(env: Env, schema_id: BytesN<32>) -> Option<String> {
+        require_schema_exists(&env, &schema_id);
+        env.storage()
+            .persistent()
+            .get(&DataKey::SchemaDefinition(schema_id))
+    }
+
+    pub fn set_validity_policy(
+        env: Env,
+        owner: Address,
+        schema_id: BytesN<32>,
+        min_validity: Option<u64>,
+        max_validity: Option<u64>,
+        expiration_required: bool,
+    ) {
+        let mut schema = require_schema_exists(&env, &schema_id);
+        require_schema_owner(&schema, &owner);
+
+        let constrained = min_validity.is_some() || max_validity.is_some() || expiration_required;
+        if constrained && !schema.expires_allowed {
+            panic!("expiration_not_allowed");
+        }
+        if let (Some(min), Some(max)) = (min_validity, max_validity) {
+            if min > max {
+                panic!("invalid_validity_policy");
+            }
+        }
+
+        schema.min_validity = min_validity;
+        schema.max_validity = max_validity;
+        schema.expiration_required = expiration_required;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Schema(schema_id.clone()), &schema);
+
+        // Event: ValidityPolicyUpdated(schema_id, min_validity, max_validity, expiration_required, timestamp)
+        emit_validity_policy_updated(&env, &schema_id, &schema);
+    }
+
+    // Lifecycle only moves forward: active -> deprecated -> retired.
+    pub fn set_schema_status(
+        env: Env,
+        owner: Address,
+        schema_id: BytesN<32>,
+        status: u32,
+        successor_schema_id: Option<BytesN<32>>,
+    ) {
+        let mut schema = require_schema_exists(&env, &schema_id);
+        require_schema_owner(&schema, &owner);
+
+        if status != STATUS_DEPRECATED && status != STATUS_RETIRED {
+            panic!("invalid_schema_status");
+        }
+        if status < schema.status {
+            panic!("invalid_status_transition");
+        }
+        // Retirement is final, so one ledger is enough for `verify_at`.
+        if status == STATUS_RETIRED && schema.status != STATUS_RETIRED {
+            env.storage().persistent().set(
+                &DataKey::SchemaRetiredAt(schema_id.clone()),
+                &now_ledger(&env),
+            );
+        }
+
+        if let Some(successor) = &successor_schema_id {
+            if *successor == schema_id {
+                panic!("invalid_successor");
+            }
+            require_schema_exists(&env, successor);
+        }
+
+        schema.status = status;
+        schema.successor_schema_id = successor_schema_id;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Schema(schema_id.clone()), &schema);
+
+        // Event: SchemaStatusChanged(schema_id, status, successor_schema_id, timestamp)
+        emit_schema_status_changed(&env, &schema_id, &schema);
+    }
+
+    pub fn set_schema_fee(env: Env, owner: Address, schema_id: BytesN<32>, fee: Option<FeeConfig>) {
+        let schema = require_schema_exists(&env, &schema_id);
+        require_schema_owner(&schema, &owner);
+
+        let key = DataKey::SchemaFee(schema_id.clone());
+        match &fee {
+            Some(f) => {
+                if f.amount <= 0 {
+                    panic!("invalid_fee_amount");
+                }
+                env.storage().persistent().set(&key, f);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+
+        // Event: SchemaFeeUpdated(schema_id, fee, timestamp)
+        emit_schema_fee_updated(&env, &schema_id, &fee);
+    }
+
+    pub fn get_schema_fee(env: Env, schema_id: BytesN<32>) -> Option<FeeConfig> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SchemaFee(schema_id))
+    }
+
+    // Only affects attestations issued afterwards.
+    pub fn set_requires_subject_consent(
+        env: Env,
+        owner: Address,
+        schema_id: BytesN<32>,
+        required: bool,
+    ) {
+        let mut schema = require_schema_exists(&env, &schema_id);
+        require_schema_owner(&schema, &owner);
+
+        schema.requires_subject_consent = required;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Schema(schema_id.clone()), &schema);
+
+        // Event: SchemaFlagUpdated(schema_id, flag, value, timestamp)
+        emit_schema_flag_updated(&env, &schema_id, "requires_subject_consent", required);
+    }
+
+    pub fn set_subject_can_renounce(
+        env: Env,
+        owner: Address,
+        schema_id: BytesN<32>,
+        allowed: bool,
+    ) {
+        let mut schema = require_schema_exists(&env, &schema_id);
+        require_schema_owner(&schema, &owner);
+
+        schema.subject_can_renounce = allowed;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Schema(schema_id.clone()), &schema);
+
+        // Event: SchemaFlagUpdated(schema_id, flag, value, timestamp)
+        emit_schema_flag_updated(&env, &schema_id, "subject_can_renounce", allowed);
+    }
+
+    // Only affects attestations proposed afterwards; pending ones are checked
+    // against the configuration in force when they are cosigned, and their
+    // proposer can withdraw them with `cancel_proposal`.
+    pub fn set_multisig(
+        env: Env,
+        owner: Address,
+        schema_id: BytesN<32>,
+        config: Option<MultisigConfig>,
+    ) {
+        let schema = require_schema_exists(&env, &schema_id);
+        require_schema_owner(&schema, &owner);
+
+        let key = DataKey::SchemaMultisig(schema_id.clone());
+        match &config {
+            Some(c) => {
+                let n = c.signers.len();
+                if n == 0 || n > MAX_MULTISIG_SIGNERS || c.threshold == 0 || c.threshold > n {
+                    panic!("invalid_multisig_config");
+                }
+                for (i, signer) in c.signers.iter().enumerate() {
+                    if c.signers.first_index_of(&signer) != Some(i as u32) {
+                        panic!("duplicate_signer");
+                    }
+                }
+                env.storage().persistent().set(&key, c);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+
+        // Event: SchemaMultisigUpdated(schema_id, config, timestamp)
+        emit_schema_multisig_updated(&env, &schema_id, &config);
+    }
+
+    pub fn get_multisig(env: Env, schema_id: BytesN<32>) -> Option<MultisigConfig> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SchemaMultisig(schema_id))
+    }
+
+    // The payer still has to authorize every `attest` it pays for; this only
+    // tells `attest` whom to charge.
+    pub fn set_fee_payer(env: Env, attester: Address, payer: Option<Address>) {
+        attester.require_auth();
+        let key = DataKey::FeePayer(attester.clone());
+        match &payer {
+            Some(p) => env.storage().persistent().set(&key, p),
+            None => env.storage().persistent().remove(&key),
+        }
+
+        // Event: FeePayerUpdated(attester, payer, timestamp)
+        emit_fee_payer_updated(&env, &attester, &payer);
+    }
+
+    pub fn get_fee_payer(env: Env, attester: Address) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::FeePayer(attester))
+    }
+
+    // Anti-replay helper
+    pub fn get_nonce(env: Env, attester: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Nonce(attester))
+            .unwrap_or(0)
+    }
+
+    // RF.C.02
+    #[allow(clippy::too_many_arguments)]
+    pub fn attest(
+        env: Env,
+        attester: Address,
+        schema_id: BytesN<32>,
+        subject: Address,
+        data_hash: BytesN<32>,
+        valid_from: Option<u64>,
+        expiration: Option<u64>,
+        nonce: u64,
+    ) -> BytesN<32> {
+        attester.require_auth();
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::SchemaMultisig(schema_id.clone()))
+        {
+            panic!("multisig_required");
+        }
+
+        issue_attestation(
+            &env, &attester, &schema_id, &subject, &data_hash, valid_from, expiration, nonce, false,
+        )
+    }
+
+    // Same as `attest`, with `data_hash` computed on-chain from the claim
+    // values and the schema definition. Not for commitment schemas, whose
+    // claims must stay off-chain.
+    #[allow(clippy::too_many_arguments)]
+    pub fn attest_with_data(
+        env: Env,
+        attester: Address,
+        schema_id: BytesN<32>,
+        subject: Address,
+        values: Vec<ClaimValue>,
+        valid_from: Option<u64>,
+        expiration: Option<u64>,
+        nonce: u64,
+    ) -> BytesN<32> {
+        let schema = require_schema_exists(&env, &schema_id);
+        if schema.salted_commitments || schema.merkle_claims {
+            panic!("commitment_schema");
+        }
+        let data_hash = Self::hash_data(env.clone(), schema_id.clone(), values);
+        Self::attest(
+            env, attester, schema_id, subject, data_hash, valid_from, expiration, nonce,
+        )
+    }
+
+    // Threshold attestations start pending and only become valid (and emit
+    // `Attested`) once enough of the schema's signers have cosigned.
+    #[allow(clippy::too_many_arguments)]
+    pub fn propose_attestation(
+        env: Env,
+        proposer: Address,
+        schema_id: BytesN<32>,
+        subject: Address,
+        data_hash: BytesN<32>,
+        valid_from: Option<u64>,
+        expiration: Option<u64>,
+        nonce: u64,
+    ) -> BytesN<32> {
+        proposer.require_auth();
+        let config: MultisigConfig = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SchemaMultisig(schema_id.clone()))
+            .unwrap_or_else(|| panic!("multisig_not_configured"));
+        if !config.signers.contains(&proposer) {
+            panic!("not_signer");
+        }
+
+        let attestation_id = issue_attestation(
+            &env, &proposer, &schema_id, &subject, &data_hash, valid_from, expiration, nonce, true,
+        );
+        env.storage().persistent().set(
+            &DataKey::Signers(attestation_id.clone()),
+            &Vec::from_array(&env, [proposer.clone()]),
+        );
+
+        // Event: AttestationProposed(attestation_id, schema_id, proposer, subject, data_hash, timestamp)
+        emit_attestation_proposed(
+            &env,
+            &attestation_id,
+            &schema_id,
+            &proposer,
+            &subject,
+            &data_hash,
+        );
+
+        // The proposer's own signature already meets a threshold of one.
+        if config.threshold <= 1 {
+            let mut att = require_attestation_exists(&env, &attestation_id);
+            complete_proposal(&env, &attestation_id, &mut att);
+        }
+
+        attestation_id
+    }
+
+    // Withdraws a proposal that has not reached its threshold, e.g. after
+    // `set_multisig` removed the signers it was waiting for. The fee and the
+    // nonce stay spent.
+    pub fn cancel_proposal(env: Env, proposer: Address, attestation_id: BytesN<32>) {
+        proposer.require_auth();
+        let att = require_attestation_exists(&env, &attestation_id);
+
+        if !att.pending_signatures {
+            panic!("not_pending");
+        }
+        if proposer != att.attester {
+            panic!("not_attester");
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Attestation(attestation_id.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Signers(attestation_id.clone()));
+
+        // Event: ProposalCancelled(attestation_id, proposer, timestamp)
+        emit_proposal_cancelled(&env, &attestation_id, &proposer);
+    }
+
+    pub fn cosign(env: Env, signer: Address, attestation_id: BytesN<32>) {
+        signer.require_auth();
+        let mut att = require_attestation_exists(&env, &attestation_id);
+
+        if !att.pending_signatures {
+            panic!("not_pending");
+        }
+        if is_revoked(&env, &att) {
+            panic!("attestation_revoked");
+        }
+
+        let config: MultisigConfig = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SchemaMultisig(att.schema_id.clone()))
+            .unwrap_or_else(|| panic!("multisig_not_configured"));
+        if !config.signers.contains(&signer) {
+            panic!("not_signer");
+        }
+
+        let signers_key = DataKey::Signers(attestation_id.clone());
+        let mut signers: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&signers_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        if signers.contains(&signer) {
+            panic!("already_signed");
+        }
+        signers.push_back(signer.clone());
+        env.storage().persistent().set(&signers_key, &signers);
+
+        // Signers dropped by `set_multisig` since they signed no longer count.
+        let signatures = signers
+            .iter()
+            .filter(|s| config.signers.contains(s))
+            .count() as u32;
+
+        // Event: Cosigned(attestation_id, signer, signatures, timestamp)
+        emit_cosigned(&env, &attestation_id, &signer, signatures);
+
+        if signatures >= config.threshold {
+            complete_proposal(&env, &attestation_id, &mut att);
+        }
+    }
+
+    // Anchors a whole batch of credentials as one record. Leaves are
+    // `sha256(0x00 || index_be || subject_xdr || data_hash)` in a positional
+    // sha256 tree with `0x01`-tagged nodes; see `verify_in_batch`.
+    pub fn attest_merkle_root(
+        env: Env,
+        attester: Address,
+        schema_id: BytesN<32>,
+        root: BytesN<32>,
+        count: u32,
+        expiration: Option<u64>,
+    ) -> BytesN<32> {
+        attester.require_auth();
+        let schema = require_schema_exists(&env, &schema_id);
+
+        match schema.status {
+            STATUS_DEPRECATED => panic!("schema_deprecated"),
+            STATUS_RETIRED => panic!("schema_retired"),
+            _ => {}
+        }
+        if count == 0 || count > MAX_BATCH_SIZE {
+            panic!("invalid_batch_size");
+        }
+        if expiration.is_some() && !schema.expires_allowed {
+            panic!("expiration_not_allowed");
+        }
+        check_validity_period(&env, &schema, None, expiration);
+        if schema.attester_mode == MODE_ISSUER_ONLY && attester != schema.creator {
+            panic!("issuer_only");
+        }
+        // Per-subject consent and cosigning cannot be expressed for a batch.
+        if schema.requires_subject_consent {
+            panic!("subject_consent_required");
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::SchemaMultisig(schema_id.clone()))
+        {
+            panic!("multisig_required");
+        }
+
+        let fee_paid = charge_attestation_fee(&env, &schema_id, &attester, count);
+
+        let root_id = next_batch_id(&env);
+        let batch = BatchAttestation {
+            schema_id,
+            attester,
+            root,
+            count,
+            timestamp: now_ledger(&env),
+            expiration,
+            revoked: false,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Batch(root_id.clone()), &batch);
+
+        // Event: BatchAttested(root_id, schema_id, attester, root, count, timestamp, expiration)
+        emit_batch_attested(&env, &root_id, &batch);
+
+        if let Some((payer, fee)) = fee_paid {
+            // Event: FeePaid(root_id, payer, token, recipient, amount, timestamp)
+            emit_fee_paid(&env, &root_id, &payer, &fee);
+        }
+
+        root_id
+    }
+
+    pub fn revoke_batch(env: Env, revoker: Address, root_id: BytesN<32>) {
+        revoker.require_auth();
+        let mut batch = require_batch_exists(&env, &root_id);
+
+        if revoker != batch.attester {
+            panic!("not_attester");
+        }
+        if !require_schema_exists(&env, &batch.schema_id).revocable {
+            panic!("not_revocable");
+        }
+
+        if batch.revoked {
+            return;
+        }
+
+        batch.revoked = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Batch(root_id.clone()), &batch);
+
+        // Event: BatchRevoked(root_id, revoker, timestamp)
+        emit_batch_revoked(&env, &root_id, &revoker);
+    }
+
+    pub fn revoke_batch_leaves(env: Env, revoker: Address, root_id: BytesN<32>, indices: Vec<u32>) {
+        revoker.require_auth();
+        let batch = require_batch_exists(&env, &root_id);
+
+        if revoker != batch.attester {
+            panic!("not_attester");
+        }
+        if !require_schema_exists(&env, &batch.schema_id).revocable {
+            panic!("not_revocable");
+        }
+        for index in indices.iter() {
+            if index >= batch.count {
+                panic!("invalid_leaf_index");
+            }
+        }
+
+        bitmap_set(
+            &env,
+            |chunk| DataKey::BatchRevocations(root_id.clone(), chunk),
+            &indices,
+        );
+
+        // Event: BatchLeavesRevoked(root_id, revoker, indices, timestamp)
+        emit_batch_leaves_revoked(&env, &root_id, &revoker, &indices);
+    }
+
+    // True only if the leaf is in the batch and neither the batch nor the leaf
+    // is revoked or expired.
+    pub fn verify_in_batch(
+        env: Env,
+        root_id: BytesN<32>,
+        leaf: BatchLeaf,
+        proof: Vec<BytesN<32>>,
+    ) -> bool {
+        let batch: BatchAttestation = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::Batch(root_id.clone()))
+        {
+            Some(b) => b,
+            None => return false,
+        };
+
+        if batch.revoked || leaf.index >= batch.count {
+            return false;
+        }
+        if let Some(exp) = batch.expiration {
+            if now_ledger(&env) >= exp {
+                return false;
+            }
+        }
+        if require_schema_exists(&env, &batch.schema_id).status == STATUS_RETIRED {
+            return false;
+        }
+
+        let leaf_hash = batch_leaf_hash(&env, &leaf);
+        if merkle_root_from_indexed_proof(&env, &leaf_hash, leaf.index, &proof) != batch.root {
+            return false;
+        }
+
+        !bitmap_get(
+            &env,
+            |chunk| DataKey::BatchRevocations(root_id.clone(), chunk),
+            leaf.index,
+        )
+    }
+
+    pub fn get_batch(env: Env, root_id: BytesN<32>) -> BatchAttestation {
+        require_batch_exists(&env, &root_id)
+    }
+
+    pub fn get_signers(env: Env, attestation_id: BytesN<32>) -> Vec<Address> {
+        let att = require_attestation_exists(&env, &attestation_id);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Signers(attestation_id))
+            .unwrap_or_else(|| Vec::from_array(&env, [att.attester]))
+    }
+
+    // RF.C.03
+    pub fn revoke_by(env: Env, revoker: Address, attestation_id: BytesN<32>) {
+        revoker.require_auth();
+        let mut att = require_attestation_exists(&env, &attestation_id);
+
+        if revoker != att.attester {
+            panic!("not_attester");
+        }
+
+        let schema = require_schema_exists(&env, &att.schema_id);
+        if !schema.revocable {
+            panic!("not_revocable");
+        }
+
+        // Also covers bulk revocation through the status list.
+        if is_revoked(&env, &att) {
+            return;
+        }
+
+        att.revoked = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Attestation(attestation_id.clone()), &att);
+        record_status(&env, &attestation_id, StatusKind::Revoked);
+
+        // Event: Revoked(attestation_id, revoker, timestamp)
+        emit_revoked(&env, &attestation_id, &att, &revoker);
+    }
+
+    // Temporary revocation, undone by `reinstate`. Same rules as `revoke_by`.
+    pub fn suspend(env: Env, attester: Address, attestation_id: BytesN<32>) {
+        set_suspended(&env, &attester, &attestation_id, true);
+    }
+
+    pub fn reinstate(env: Env, attester: Address, attestation_id: BytesN<32>) {
+        set_suspended(&env, &attester, &attestation_id, false);
+    }
+
+    // Bulk revocation: flips the given bits of the revoker's status list
+    // (W3C Status List 2021 style). Indices come from `Attestation.status_index`.
+    pub fn revoke_status_indices(env: Env, revoker: Address, indices: Vec<u32>) {
+        revoker.require_auth();
+
+        let len: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::StatusListLen(revoker.clone()))
+            .unwrap_or(0);
+        for index in indices.iter() {
+            if index >= len {
+                panic!("invalid_status_index");
+            }
+        }
+
+        bitmap_set(
+            &env,
+            |chunk| DataKey::StatusList(revoker.clone(), chunk),
+            &indices,
+        );
+
+        // The bitmap only says "revoked"; `verify_at` also needs when.
+        let now = now_ledger(&env);
+        for index in indices.iter() {
+            let key = DataKey::StatusRevokedAt(revoker.clone(), index);
+            if !env.storage().persistent().has(&key) {
+                env.storage().persistent().set(&key, &now);
+            }
+        }
+
+        // Event: StatusListRevoked(revoker, indices, timestamp)
+        emit_status_list_revoked(&env, &revoker, &indices);
+    }
+
+    pub fn get_status_list_chunk(env: Env, attester: Address, chunk: u32) -> Bytes {
+        env.storage()
+            .persistent()
+            .get(&DataKey::StatusList(attester, chunk))
+            .unwrap_or_else(|| Bytes::new(&env))
+    }
+
+    // Off-chain attestations: anyone may anchor a hash; the first ledger wins.
+    pub fn timestamp(env: Env, data: BytesN<32>) -> TimestampRecord {
+        record_timestamp(&env, &data)
+    }
+
+    pub fn multi_timestamp(env: Env, data: Vec<BytesN<32>>) -> Vec<TimestampRecord> {
+        let mut records = Vec::new(&env);
+        for d in data.iter() {
+            records.push_back(record_timestamp(&env, &d));
+        }
+        records
+    }
+
+    pub fn get_timestamp(env: Env, data: BytesN<32>) -> Option<TimestampRecord> {
+        env.storage().persistent().get(&DataKey::Timestamp(data))
+    }
+
+    // Revocations are scoped to the signer, so nobody can revoke someone
+    // else's off-chain attestation.
+    pub fn revoke_offchain(env: Env, signer: Address, data: BytesN<32>) -> TimestampRecord {
+        signer.require_auth();
+        let key = DataKey::OffchainRevocation(signer.clone(), data.clone());
+        if let Some(existing) = env.storage().persistent().get(&key) {
+            return existing;
+        }
+
+        let record = now_record(&env);
+        env.storage().persistent().set(&key, &record);
+
+        // Event: RevokedOffchain(signer, data, ledger, time)
+        emit_revoked_offchain(&env, &signer, &data, &record);
+        record
+    }
+
+    pub fn get_offchain_revocation(
+        env: Env,
+        signer: Address,
+        data: BytesN<32>,
+    ) -> Option<TimestampRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OffchainRevocation(signer, data))
+    }
+
+    pub fn accept_attestation(env: Env, subject: Address, attestation_id: BytesN<32>) {
+        set_consent(&env, &subject, &attestation_id, CONSENT_ACCEPTED);
+        record_status(&env, &attestation_id, StatusKind::Accepted);
+        // Event: AttestationAccepted(attestation_id, subject, timestamp)
+        emit_consent(&env, "AttestationAccepted", &attestation_id, &subject);
+    }
+
+    pub fn reject_attestation(env: Env, subject: Address, attestation_id: BytesN<32>) {
+        set_consent(&env, &subject, &attestation_id, CONSENT_REJECTED);
+        record_status(&env, &attestation_id, StatusKind::Rejected);
+        // Event: AttestationRejected(attestation_id, subject, timestamp)
+        emit_consent(&env, "AttestationRejected", &attestation_id, &subject);
+    }
+
+    // Subject-side counterpart of `revoke_by`, kept distinct from issuer revocation.
+    pub fn renounce(env: Env, subject: Address, attestation_id: BytesN<32>) {
+        subject.require_auth();
+        let mut att = require_attestation_exists(&env, &attestation_id);
+
+        if subject != att.subject {
+            panic!("not_subject");
+        }
+
+        let schema = require_schema_exists(&env, &att.schema_id);
+        if !schema.subject_can_renounce {
+            panic!("renounce_not_allowed");
+        }
+
+        if att.renounced {
+            return;
+        }
+
+        att.renounced = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Attestation(attestation_id.clone()), &att);
+        record_status(&env, &attestation_id, StatusKind::Renounced);
+
+        // Event: Renounced(attestation_id, subject, timestamp)
+        emit_renounced(&env, &attestation_id, &subject);
+    }
+
+    pub fn renew(env: Env, attester: Address, attestation_id: BytesN<32>, new_expiration: u64) {
+        attester.require_auth();
+        let mut att = require_attestation_exists(&env, &attestation_id);
+
+        if attester != att.attester {
+            panic!("not_attester");
+        }
+
+        let schema = require_schema_exists(&env, &att.schema_id);
+        if !schema.expires_allowed || !schema.renewable {
+            panic!("not_renewable");
+        }
+        if schema.status == STATUS_RETIRED {
+            panic!("schema_retired");
+        }
+
+        if is_revoked(&env, &att) {
+            panic!("attestation_revoked");
+        }
+        if att.consent_status == CONSENT_REJECTED {
+            panic!("attestation_rejected");
+        }
+        if att.renounced {
+            panic!("attestation_renounced");
+        }
+        if att.pending_signatures {
+            panic!("attestation_pending");
+        }
+
+        // Renewal only ever extends an attestation that already has an expiration;
+        // one without expiration never lapses and must not be shortened.
+        let previous_expiration = match att.expiration {
+            Some(exp) => exp,
+            None => panic!("expiration_not_extended"),
+        };
+        // A lapsed attestation stays expired; `verify_at` relies on that, so
+        // the attester has to issue a new one instead.
+        if previous_expiration <= now_ledger(&env) {
+            panic!("attestation_expired");
+        }
+        if new_expiration <= previous_expiration {
+            panic!("expiration_not_extended");
+        }
+        check_validity_period(&env, &schema, att.valid_from, Some(new_expiration));
+
+        att.expiration = Some(new_expiration);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Attestation(attestation_id.clone()), &att);
+
+        let renewals_key = DataKey::Renewals(attestation_id.clone());
+        let mut renewals: Vec<Renewal> = env
+            .storage()
+            .persistent()
+            .get(&renewals_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        renewals.push_back(Renewal {
+            ledger: now_ledger(&env),
+            previous_expiration,
+            new_expiration,
+        });
+        env.storage().persistent().set(&renewals_key, &renewals);
+        record_status(&env, &attestation_id, StatusKind::Renewed);
+
+        // Event: Renewed(attestation_id, attester, previous_expiration, new_expiration, timestamp)
+        emit_renewed(
+            &env,
+            &attestation_id,
+            &attester,
+            previous_expiration,
+            new_expiration,
+        );
+    }
+
+    pub fn get_renewals(env: Env, attestation_id: BytesN<32>) -> Vec<Renewal> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Renewals(attestation_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    // RF.C.04
+    pub fn verify(env: Env, attestation_id: BytesN<32>) -> Option<VerifyResult> {
+        let att: Option<Attestation> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Attestation(attestation_id.clone()));
+
+        let att = att?;
+
+        let now = now_ledger(&env);
+        let expired = match att.expiration {
+            Some(exp) => now >= exp,
+            None => false,
+        };
+        let not_yet_valid = match att.valid_from {
+            Some(from) => now < from,
+            None => false,
+        };
+        let revoked = is_revoked(&env, &att);
+        let renounced = att.renounced;
+        let suspended = att.suspended;
+
+        // Deprecated schemas keep existing attestations valid; retired ones do not.
+        let schema_status = require_schema_exists(&env, &att.schema_id).status;
+        let consented =
+            att.consent_status == CONSENT_NOT_REQUIRED || att.consent_status == CONSENT_ACCEPTED;
+        let pending_signatures = att.pending_signatures;
+        let signers = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Signers(attestation_id))
+            .unwrap_or_else(|| Vec::from_array(&env, [att.attester.clone()]));
+        let valid = !revoked
+            && !pending_signatures
+            && !renounced
+            && !suspended
+            && !expired
+            && !not_yet_valid
+            && consented
+            && schema_status != STATUS_RETIRED;
+
+        Some(VerifyResult {
+            exists: true,
+            valid,
+            revoked,
+            renounced,
+            suspended,
+            expired,
+            not_yet_valid,
+            schema_status,
+            consent_status: att.consent_status,
+            pending_signatures,
+            signers,
+            schema_id: att.schema_id,
+            attester: att.attester,
+            subject: att.subject,
+            data_hash: att.data_hash,
+            timestamp: att.timestamp,
+            valid_from: att.valid_from,
+            expiration: att.expiration,
+        })
+    }
+
+    // `verify` for the close of a past (or the current) ledger, rebuilt from
+    // the status history, the renewals and the schema's retirement ledger.
+    pub fn verify_at(
+        env: Env,
+        attestation_id: BytesN<32>,
+        ledger: u64,
+    ) -> Option<HistoricalStatus> {
+        if ledger > now_ledger(&env) {
+            panic!("future_ledger");
+        }
+        let att: Option<Attestation> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Attestation(attestation_id.clone()));
+        let att = att?;
+
+        let mut issued = false;
+        let mut revoked = false;
+        let mut suspended = false;
+        let mut renounced = false;
+        let mut consent_status = if att.consent_status == CONSENT_NOT_REQUIRED {
+            CONSENT_NOT_REQUIRED
+        } else {
+            CONSENT_PENDING
+        };
+        for change in status_history(&env, &attestation_id, &att).iter() {
+            if change.ledger > ledger {
+                break;
+            }
+            match change.kind {
+                StatusKind::Issued => issued = true,
+                StatusKind::Revoked => revoked = true,
+                StatusKind::Suspended => suspended = true,
+                StatusKind::Reinstated => suspended = false,
+                StatusKind::Renounced => renounced = true,
+                StatusKind::Accepted => consent_status = CONSENT_ACCEPTED,
+                StatusKind::Rejected => consent_status = CONSENT_REJECTED,
+                StatusKind::Renewed => {}
+            }
+        }
+
+        // The expiration in force is the one the first later renewal replaced.
+        let expiration = Self::get_renewals(env.clone(), attestation_id)
+            .iter()
+            .find(|r| r.ledger > ledger)
+            .map_or(att.expiration, |r| Some(r.previous_expiration));
+        let retired_at: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SchemaRetiredAt(att.schema_id));
+
+        let exists = ledger >= att.timestamp;
+        let pending_signatures = exists && !issued;
+        let expired = expiration.is_some_and(|exp| ledger >= exp);
+        let not_yet_valid = att.valid_from.is_some_and(|from| ledger < from);
+        let schema_retired = retired_at.is_some_and(|at| at <= ledger);
+        let consented =
+            consent_status == CONSENT_NOT_REQUIRED || consent_status == CONSENT_ACCEPTED;
+        let valid = exists
+            && !pending_signatures
+            && !revoked
+            && !suspended
+            && !renounced
+            && !expired
+            && !not_yet_valid
+            && consented
+            && !schema_retired;
+
+        Some(HistoricalStatus {
+            ledger,
+            exists,
+            valid,
+            revoked,
+            renounced,
+            suspended,
+            expired,
+            not_yet_valid,
+            schema_retired,
+            consent_status,
+            pending_signatures,
+            expiration,
+        })
+    }
+
+    pub fn get_status_history(env: Env, attestation_id: BytesN<32>) -> Vec<StatusChange> {
+        let att = require_attestation_exists(&env, &attestation_id);
+        status_history(&env, &attestation_id, &att)
+    }
+
+    // `verify` plus the caller's acceptance policy, reporting every failed
+    // condition instead of a single boolean. The attester checked against
+    // `policy.attesters` is the record's attester (the proposer for threshold
+    // attestations).
+    pub fn verify_with_policy(
+        env: Env,
+        attestation_id: BytesN<32>,
+        policy: VerifyPolicy,
+    ) -> PolicyResult {
+        let now = now_ledger(&env);
+        let mut failures = Vec::new(&env);
+        match Self::verify(env.clone(), attestation_id) {
+            None => failures.push_back(PolicyFailure::NotFound),
+            Some(r) => {
+                let checks = [
+                    (r.revoked, PolicyFailure::Revoked),
+                    (r.renounced, PolicyFailure::Renounced),
+                    (r.suspended, PolicyFailure::Suspended),
+                    (r.expired, PolicyFailure::Expired),
+                    (r.not_yet_valid, PolicyFailure::NotYetValid),
+                    (
+                        r.consent_status == CONSENT_PENDING,
+                        PolicyFailure::ConsentPending,
+                    ),
+                    (
+                        r.consent_status == CONSENT_REJECTED,
+                        PolicyFailure::ConsentRejected,
+                    ),
+                    (r.pending_signatures, PolicyFailure::PendingSignatures),
+                    (
+                        r.schema_status == STATUS_RETIRED,
+                        PolicyFailure::SchemaRetired,
+                    ),
+                    (
+                        policy.schema_id.is_some_and(|id| id != r.schema_id),
+                        PolicyFailure::WrongSchema,
+                    ),
+                    (
+                        !policy.attesters.is_empty() && !policy.attesters.contains(&r.attester),
+                        PolicyFailure::UntrustedAttester,
+                    ),
+                    (
+                        policy
+                            .max_age
+                            .is_some_and(|max| now.saturating_sub(r.timestamp) > max),
+                        PolicyFailure::TooOld,
+                    ),
+                    (
+                        policy.min_remaining_validity.is_some_and(|min| {
+                            r.expiration
+                                .is_some_and(|exp| exp.saturating_sub(now) < min)
+                        }),
+                        PolicyFailure::ExpiresTooSoon,
+                    ),
+                    (
+                        policy.subject.is_some_and(|s| s != r.subject),
+                        PolicyFailure::WrongSubject,
+                    ),
+                ];
+                for (failed, failure) in checks {
+                    if failed {
+                        failures.push_back(failure);
+                    }
+                }
+            }
+        }
+
+        PolicyResult {
+            valid: failures.is_empty(),
+            failures,
+            ledger: now,
+        }
+    }
+
+    // Checks a disclosed claim against the attestation's commitment. Callers
+    // still need `verify` to know whether the attestation itself is valid.
+    pub fn verify_claim(
+        env: Env,
+        attestation_id: BytesN<32>,
+        preimage: Bytes,
+        salt: BytesN<32>,
+    ) -> bool {
+        let att = require_attestation_exists(&env, &attestation_id);
+        let schema = require_schema_exists(&env, &att.schema_id);
+        if !schema.salted_commitments {
+            panic!("not_commitment_schema");
+        }
+
+        let mut msg = Bytes::from_array(&env, &salt.to_array());
+        msg.append(&preimage);
+        let commitment: BytesN<32> = env.crypto().sha256(&msg).into();
+        commitment == att.data_hash
+    }
+
+    // Canonical encoding of the claim values under the schema definition; for
+    // salted-commitment schemas this is the `preimage` of `verify_claim`.
+    pub fn encode_data(env: Env, schema_id: BytesN<32>, values: Vec<ClaimValue>) -> Bytes {
+        require_schema_exists(&env, &schema_id);
+        let definition: String = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SchemaDefinition(schema_id))
+            .unwrap_or_else(|| panic!("schema_definition_not_set"));
+        encoder::encode_claim(&env, &definition, &values)
+    }
+
+    pub fn hash_data(env: Env, schema_id: BytesN<32>, values: Vec<ClaimValue>) -> BytesN<32> {
+        let encoded = Self::encode_data(env.clone(), schema_id, values);
+        env.crypto().sha256(&encoded).into()
+    }
+
+    // `field` is the disclosed field itself; its leaf is computed here, see
+    // `merkle_field_leaf`. Issuers should salt low-entropy fields before
+    // putting them in the tree. Like `verify_claim`, this does not check the
+    // attestation's own validity.
+    pub fn verify_field(
+        env: Env,
+        attestation_id: BytesN<32>,
+        field: Bytes,
+        proof: Vec<BytesN<32>>,
+    ) -> bool {
+        let att = require_attestation_exists(&env, &attestation_id);
+        let schema = require_schema_exists(&env, &att.schema_id);
+        if !schema.merkle_claims {
+            panic!("not_merkle_schema");
+        }
+
+        let leaf = merkle_field_leaf(&env, &field);
+        merkle_root_from_proof(&env, &leaf, &proof) == att.data_hash
+    }
+
+    pub fn get_schema(env: Env, schema_id: BytesN<32>) -> Schema {
+        require_schema_exists(&env, &schema_id)
+    }
+
+    pub fn get_attestation(env: Env, attestation_id: BytesN<32>) -> Attestation {
+        require_attestation_exists(&env, &attestation_id)
+    }
+
+    // Head of the event hash chain; an indexer that has replayed events
+    // 1..=seq must arrive at the same hash.
+    pub fn get_state_checkpoint(env: Env) -> StateCheckpoint {
+        let storage = env.storage().instance();
+        StateCheckpoint {
+            seq: storage.get(&DataKey::EventSeq).unwrap_or(0),
+            hash: storage
+                .get(&DataKey::StateHash)
+                .unwrap_or_else(|| BytesN::from_array(&env, &[0u8; 32])),
+            ledger: now_ledger(&env),
+        }
+    }
+
+    pub fn version(env: Env) -> Symbol {
+        Symbol::new(&env, "v0.1")
+    }
+}
+
+// Not exported (the `#[contractimpl]` functions are): this only makes any
+// drift between the contract and the published interface a compile error.
+impl eas_interface::EasInterface for EasContract {
+    fn verify(env: Env, attestation_id: BytesN<32>) -> Option<VerifyResult> {
+        Self::verify(env, attestation_id)
+    }
+
+    fn verify_with_policy(
+        env: Env,
+        attestation_id: BytesN<32>,
+        policy: VerifyPolicy,
+    ) -> PolicyResult {
+        Self::verify_with_policy(env, attestation_id, policy)
+    }
+
+    fn verify_at(env: Env, attestation_id: BytesN<32>, ledger: u64) -> Option<HistoricalStatus> {
+        Self::verify_at(env, attestation_id, ledger)
+    }
+
+    fn get_status_history(env: Env, attestation_id: BytesN<32>) -> Vec<StatusChange> {
+        Self::get_status_history(env, attestation_id)
+    }
+
+    fn get_schema(env: Env, schema_id: BytesN<32>) -> Schema {
+        Self::get_schema(env, schema_id)
+    }
+
+    fn get_attestation(env: Env, attestation_id: BytesN<32>) -> Attestation {
+        Self::get_attestation(env, attestation_id)
+    }
+
+    fn get_renewals(env: Env, attestation_id: BytesN<32>) -> Vec<Renewal> {
+        Self::get_renewals(env, attestation_id)
+    }
+
+    fn verify_in_batch(
+        env: Env,
+        root_id: BytesN<32>,
+        leaf: BatchLeaf,
+        proof: Vec<BytesN<32>>,
+    ) -> bool {
+        Self::verify_in_batch(env, root_id, leaf, proof)
+    }
+
+    fn get_batch(env: Env, root_id: BytesN<32>) -> BatchAttestation {
+        Self::get_batch(env, root_id)
+    }
+
+    fn verify_claim(
+        env: Env,
+        attestation_id: BytesN<32>,
+        preimage: Bytes,
+        salt: BytesN<32>,
+    ) -> bool {
+        Self::verify_claim(env, attestation_id, preimage, salt)
+    }
+
+    fn verify_field(
+        env: Env,
+        attestation_id: BytesN<32>,
+        field: Bytes,
+        proof: Vec<BytesN<32>>,
+    ) -> bool {
+        Self::verify_field(env, attestation_id, field, proof)
+    }
+
+    fn get_timestamp(env: Env, data: BytesN<32>) -> Option<TimestampRecord> {
+        Self::get_timestamp(env, data)
+    }
+
+    fn get_offchain_revocation(
+        env: Env,
+        signer: Address,
+        data: BytesN<32>,
+    ) -> Option<TimestampRecord> {
+        Self::get_offchain_revocation(env, signer, data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::*;
+    use soroban_sdk::testutils::EnvTestConfig;
+    use soroban_sdk::testutils::{Address as _, Ledger as _, LedgerInfo};
+
+    #[test]
+    fn schema_attest_verify_revoke() {
+        let mut env = Env::default();
+        env.set_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.mock_all_auths();
+        env.ledger().set(LedgerInfo {
+            timestamp: 0,
+            protocol_version: 25,
+            sequence_number: 1,
+            network_id: [0; 32],
+            base_reserve: 10,
+            min_temp_entry_ttl: 1,
+            min_persistent_entry_ttl: 1,
+            max_entry_ttl: 100,
+        });
+
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[7u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &false, &0u32);
+        assert_eq!(schema_id, schema_hash);
+
+        let data_hash = BytesN::from_array(&env, &[9u8; 32]);
+        let att_id = client.attest(
+            &attester, &schema_id, &subject, &data_hash, &None, &None, &1u64,
+        );
+
+        let vr = client.verify(&att_id).unwrap();
+        assert!(vr.exists);
+        assert!(vr.valid);
+        assert!(!vr.revoked);
+        assert!(!vr.expired);
+
+        client.revoke_by(&attester, &att_id);
+        let vr2 = client.verify(&att_id).unwrap();
+        assert!(vr2.exists);
+        assert!(!vr2.valid);
+        assert!(vr2.revoked);
+    }
+
+    #[test]
+    #[should_panic(expected = "issuer_only")]
+    fn issuer_only_enforced() {
+        let mut env = Env::default();
+        env.set_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.mock_all_auths();
+
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[1u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &false, &1u32);
+
+        let data_hash = BytesN::from_array(&env, &[2u8; 32]);
+        client.attest(
+            &attester, &schema_id, &subject, &data_hash, &None, &None, &1u64,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "bad_nonce")]
+    fn bad_nonce_rejected() {
+        let mut env = Env::default();
+        env.set_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.mock_all_auths();
+
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[3u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &false, &0u32);
+
+        let data_hash = BytesN::from_array(&env, &[4u8; 32]);
+        // First nonce must be 1.
+        client.attest(
+            &attester, &schema_id, &subject, &data_hash, &None, &None, &2u64,
+        );
+    }
+
+    fn setup_env(sequence_number: u32) -> Env {
+        let mut env = Env::default();
+        env.set_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.mock_all_auths();
+        set_sequence(&env, sequence_number);
+        env
+    }
+
+    fn set_sequence(env: &Env, sequence_number: u32) {
+        env.ledger().set(LedgerInfo {
+            timestamp: 0,
+            protocol_version: 25,
+            sequence_number,
+            network_id: [0; 32],
+            base_reserve: 10,
+            min_temp_entry_ttl: 1,
+            min_persistent_entry_ttl: 1,
+            max_entry_ttl: 100,
+        });
+    }
+
+    #[test]
+    fn valid_from_defers_validity() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[5u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &true, &0u32);
+
+        let data_hash = BytesN::from_array(&env, &[6u8; 32]);
+        let att_id = client.attest(
+            &attester,
+            &schema_id,
+            &subject,
+            &data_hash,
+            &Some(20u64),
+            &Some(30u64),
+            &1u64,
+        );
+
+        let vr = client.verify(&att_id).unwrap();
+        assert!(vr.not_yet_valid);
+        assert!(!vr.valid);
+        assert_eq!(vr.valid_from, Some(20));
+
+        // Becomes valid exactly at valid_from.
+        set_sequence(&env, 20);
+        let vr = client.verify(&att_id).unwrap();
+        assert!(!vr.not_yet_valid);
+        assert!(vr.valid);
+
+        set_sequence(&env, 30);
+        let vr = client.verify(&att_id).unwrap();
+        assert!(!vr.not_yet_valid);
+        assert!(vr.expired);
+        assert!(!vr.valid);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid_validity_window")]
+    fn valid_from_must_precede_expiration() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[5u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &true, &0u32);
+
+        let data_hash = BytesN::from_array(&env, &[6u8; 32]);
+        client.attest(
+            &attester,
+            &schema_id,
+            &subject,
+            &data_hash,
+            &Some(30u64),
+            &Some(30u64),
+            &1u64,
+        );
+    }
+
+    #[test]
+    fn renew_extends_expiration_and_records_history() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[8u8; 32]);
+        let schema_id = client.create_schema_with_config(
+            &creator,
+            &schema_hash,
+            &true,
+            &true,
+            &0u32,
+            &SchemaConfig {
+                renewable: true,
+                ..Default::default()
+            },
+            &None,
+        );
+
+        let data_hash = BytesN::from_array(&env, &[9u8; 32]);
+        let att_id = client.attest(
+            &attester,
+            &schema_id,
+            &subject,
+            &data_hash,
+            &None,
+            &Some(20u64),
+            &1u64,
+        );
+
+        // Renewing before the attestation lapses keeps the same id valid.
+        set_sequence(&env, 15);
+        client.renew(&attester, &att_id, &50u64);
+        set_sequence(&env, 25);
+
+        let vr = client.verify(&att_id).unwrap();
+        assert!(vr.valid);
+        assert_eq!(vr.expiration, Some(50));
+
+        let renewals = client.get_renewals(&att_id);
+        assert_eq!(renewals.len(), 1);
+        assert_eq!(
+            renewals.get(0).unwrap(),
+            Renewal {
+                ledger: 15,
+                previous_expiration: 20,
+                new_expiration: 50,
+            }
+        );
+    }
+
+    fn renewable_schema(env: &Env, client: &EasContractClient, creator: &Address) -> BytesN<32> {
+        client.create_schema_with_config(
+            creator,
+            &BytesN::from_array(env, &[8u8; 32]),
+            &true,
+            &true,
+            &0u32,
+            &SchemaConfig {
+                renewable: true,
+                ..Default::default()
+            },
+            &None,
+        )
+    }
+
+    #[test]
+    #[should_panic(expected = "attestation_expired")]
+    fn renew_refuses_expired_attestation() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+        let schema_id = renewable_schema(&env, &client, &creator);
+
+        let data_hash = BytesN::from_array(&env, &[9u8; 32]);
+        let att_id = client.attest(
+            &attester,
+            &schema_id,
+            &subject,
+            &data_hash,
+            &None,
+            &Some(20u64),
+            &1u64,
+        );
+
+        // Expired from ledger 20 on; it has to be reissued, not revived.
+        set_sequence(&env, 20);
+        assert!(client.verify(&att_id).unwrap().expired);
+        client.renew(&attester, &att_id, &50u64);
+    }
+
+    #[test]
+    #[should_panic(expected = "attestation_pending")]
+    fn renew_refuses_pending_attestation() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let cosigner = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+        let schema_id = renewable_schema(&env, &client, &creator);
+        client.set_multisig(
+            &creator,
+            &schema_id,
+            &Some(MultisigConfig {
+                signers: soroban_sdk::vec![&env, attester.clone(), cosigner],
+                threshold: 2,
+            }),
+        );
+
+        let data_hash = BytesN::from_array(&env, &[9u8; 32]);
+        let att_id = client.propose_attestation(
+            &attester,
+            &schema_id,
+            &subject,
+            &data_hash,
+            &None,
+            &Some(20u64),
+            &1u64,
+        );
+        client.renew(&attester, &att_id, &50u64);
+    }
+
+    #[test]
+    #[should_panic(expected = "not_renewable")]
+    fn renew_requires_renewable_schema() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[8u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &true, &0u32);
+
+        let data_hash = BytesN::from_array(&env, &[9u8; 32]);
+        let att_id = client.attest(
+            &attester,
+            &schema_id,
+            &subject,
+            &data_hash,
+            &None,
+            &Some(20u64),
+            &1u64,
+        );
+        client.renew(&attester, &att_id, &50u64);
+    }
+
+    #[test]
+    #[should_panic(expected = "expiration_not_extended")]
+    fn renew_cannot_shorten_expiration() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[8u8; 32]);
+        let schema_id = client.create_schema_with_config(
+            &creator,
+            &schema_hash,
+            &true,
+            &true,
+            &0u32,
+            &SchemaConfig {
+                renewable: true,
+                ..Default::default()
+            },
+            &None,
+        );
+
+        let data_hash = BytesN::from_array(&env, &[9u8; 32]);
+        let att_id = client.attest(
+            &attester,
+            &schema_id,
+            &subject,
+            &data_hash,
+            &None,
+            &Some(20u64),
+            &1u64,
+        );
+        client.renew(&attester, &att_id, &15u64);
+    }
+
+    #[test]
+    fn validity_policy_enforced() {
+        let env = setup_env(100);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[10u8; 32]);
+        let schema_id = client.create_schema_with_config(
+            &creator,
+            &schema_hash,
+            &true,
+            &true,
+            &0u32,
+            &SchemaConfig {
+                renewable: true,
+                ..Default::default()
+            },
+            &None,
+        );
+        client.set_validity_policy(&creator, &schema_id, &Some(10u64), &Some(50u64), &true);
+
+        let schema = client.get_schema(&schema_id);
+        assert_eq!(schema.min_validity, Some(10));
+        assert_eq!(schema.max_validity, Some(50));
+        assert!(schema.expiration_required);
+
+        let data_hash = BytesN::from_array(&env, &[11u8; 32]);
+        let attest = |valid_from: Option<u64>, expiration: Option<u64>| {
+            let nonce = client.get_nonce(&attester) + 1;
+            client.try_attest(
+                &attester,
+                &schema_id,
+                &subject,
+                &data_hash,
+                &valid_from,
+                &expiration,
+                &nonce,
+            )
+        };
+
+        let rejected = |e: ValidityError| Err(Ok(soroban_sdk::Error::from(e)));
+        assert_eq!(
+            attest(None, None),
+            rejected(ValidityError::ExpirationRequired)
+        );
+        assert_eq!(
+            attest(None, Some(99)),
+            rejected(ValidityError::ExpirationInPast)
+        );
+        assert_eq!(
+            attest(None, Some(105)),
+            rejected(ValidityError::ValidityTooShort)
+        );
+        assert_eq!(
+            attest(None, Some(151)),
+            rejected(ValidityError::ValidityTooLong)
+        );
+        // The period is measured from valid_from when it lies in the future.
+        assert_eq!(
+            attest(Some(200), Some(205)),
+            rejected(ValidityError::ValidityTooShort)
+        );
+        assert!(attest(None, Some(110)).is_ok());
+        let att_id = attest(Some(200), Some(240)).unwrap().unwrap();
+
+        // Renewal is subject to the same maximum period.
+        assert!(client.try_renew(&attester, &att_id, &251u64).is_err());
+        client.renew(&attester, &att_id, &250u64);
+    }
+
+    #[test]
+    fn expiration_in_past_rejected() {
+        let env = setup_env(100);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[10u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &true, &0u32);
+
+        let data_hash = BytesN::from_array(&env, &[11u8; 32]);
+        // Expiring at the current ledger is already expired for `verify`.
+        for expiration in [99u64, 100] {
+            assert_eq!(
+                client.try_attest(
+                    &attester,
+                    &schema_id,
+                    &subject,
+                    &data_hash,
+                    &None,
+                    &Some(expiration),
+                    &1u64,
+                ),
+                Err(Ok(ValidityError::ExpirationInPast.into()))
+            );
+        }
+        assert!(client
+            .try_attest(
+                &attester,
+                &schema_id,
+                &subject,
+                &data_hash,
+                &None,
+                &Some(101u64),
+                &1u64
+            )
+            .is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "not_schema_owner")]
+    fn validity_policy_owner_only() {
+        let env = setup_env(100);
+        let creator = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[10u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &true, &0u32);
+        client.set_validity_policy(&other, &schema_id, &None, &Some(50u64), &false);
+    }
+
+    #[test]
+    fn schema_lifecycle() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let old_hash = BytesN::from_array(&env, &[12u8; 32]);
+        let new_hash = BytesN::from_array(&env, &[13u8; 32]);
+        let old_id = client.create_schema(&creator, &old_hash, &true, &false, &0u32);
+        let new_id = client.create_schema(&creator, &new_hash, &true, &false, &0u32);
+
+        let data_hash = BytesN::from_array(&env, &[14u8; 32]);
+        let att_id = client.attest(
+            &attester, &old_id, &subject, &data_hash, &None, &None, &1u64,
+        );
+
+        client.set_schema_status(&creator, &old_id, &STATUS_DEPRECATED, &Some(new_id.clone()));
+        let schema = client.get_schema(&old_id);
+        assert_eq!(schema.status, STATUS_DEPRECATED);
+        assert_eq!(schema.successor_schema_id, Some(new_id));
+
+        // Existing attestations stay valid, new ones are rejected.
+        let vr = client.verify(&att_id).unwrap();
+        assert!(vr.valid);
+        assert_eq!(vr.schema_status, STATUS_DEPRECATED);
+        assert!(client
+            .try_attest(&attester, &old_id, &subject, &data_hash, &None, &None, &2u64)
+            .is_err());
+
+        client.set_schema_status(&creator, &old_id, &STATUS_RETIRED, &None);
+        let vr = client.verify(&att_id).unwrap();
+        assert!(!vr.valid);
+        assert_eq!(vr.schema_status, STATUS_RETIRED);
+
+        // No way back.
+        assert!(client
+            .try_set_schema_status(&creator, &old_id, &STATUS_DEPRECATED, &None)
+            .is_err());
+    }
+
+    #[test]
+    fn schema_metadata_set_and_updated() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let metadata = SchemaMetadata {
+            name: String::from_str(&env, "Course certificate"),
+            description: String::from_str(&env, "ipfs://schemas/course-certificate.json"),
+            version: SchemaVersion {
+                major: 1,
+                minor: 0,
+                patch: 0,
+            },
+            tags: soroban_sdk::vec![&env, Symbol::new(&env, "education")],
+        };
+
+        let schema_hash = BytesN::from_array(&env, &[15u8; 32]);
+        let schema_id = client.create_schema_with_config(
+            &creator,
+            &schema_hash,
+            &true,
+            &false,
+            &0u32,
+            &SchemaConfig::default(),
+            &Some(metadata.clone()),
+        );
+        assert_eq!(
+            client.get_schema_metadata(&schema_id),
+            Some(metadata.clone())
+        );
+
+        let mut updated = metadata;
+        updated.version.minor = 1;
+        assert!(client
+            .try_update_schema_metadata(&other, &schema_id, &Some(updated.clone()))
+            .is_err());
+        client.update_schema_metadata(&creator, &schema_id, &Some(updated.clone()));
+        assert_eq!(client.get_schema_metadata(&schema_id), Some(updated));
+
+        client.update_schema_metadata(&creator, &schema_id, &None);
+        assert_eq!(client.get_schema_metadata(&schema_id), None);
+    }
+
+    #[test]
+    fn attestation_fee_is_charged() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let sponsor = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let asset_admin = Address::generate(&env);
+        let asset = env.register_stellar_asset_contract_v2(asset_admin);
+        let asset_client = token::StellarAssetClient::new(&env, &asset.address());
+        let token_client = token::Client::new(&env, &asset.address());
+        asset_client.mint(&attester, &100);
+        asset_client.mint(&sponsor, &100);
+
+        let schema_hash = BytesN::from_array(&env, &[16u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &false, &0u32);
+        client.set_schema_fee(
+            &creator,
+            &schema_id,
+            &Some(FeeConfig {
+                token: asset.address(),
+                amount: 30,
+                recipient: creator.clone(),
+            }),
+        );
+
+        let data_hash = BytesN::from_array(&env, &[17u8; 32]);
+        client.attest(
+            &attester, &schema_id, &subject, &data_hash, &None, &None, &1u64,
+        );
+        assert_eq!(token_client.balance(&attester), 70);
+        assert_eq!(token_client.balance(&creator), 30);
+
+        // A designated payer covers subsequent fees.
+        client.set_fee_payer(&attester, &Some(sponsor.clone()));
+        client.attest(
+            &attester, &schema_id, &subject, &data_hash, &None, &None, &2u64,
+        );
+        assert_eq!(token_client.balance(&attester), 70);
+        assert_eq!(token_client.balance(&sponsor), 70);
+        assert_eq!(token_client.balance(&creator), 60);
+
+        // Without enough balance the whole attestation is rolled back.
+        client.set_fee_payer(&attester, &None);
+        asset_client.burn(&attester, &50);
+        assert!(client
+            .try_attest(&attester, &schema_id, &subject, &data_hash, &None, &None, &3u64)
+            .is_err());
+        assert_eq!(client.get_nonce(&attester), 2);
+    }
+
+    #[test]
+    fn verify_with_policy_lists_failures() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let stranger = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[40u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &true, &0u32);
+        let data_hash = BytesN::from_array(&env, &[41u8; 32]);
+        let id = client.attest(
+            &attester,
+            &schema_id,
+            &subject,
+            &data_hash,
+            &None,
+            &Some(30u64),
+            &1u64,
+        );
+
+        let open = VerifyPolicy {
+            schema_id: None,
+            attesters: Vec::new(&env),
+            max_age: None,
+            min_remaining_validity: None,
+            subject: None,
+        };
+        let strict = VerifyPolicy {
+            schema_id: Some(schema_id.clone()),
+            attesters: soroban_sdk::vec![&env, attester.clone()],
+            max_age: Some(5),
+            min_remaining_validity: Some(10),
+            subject: Some(subject.clone()),
+        };
+        let r = client.verify_with_policy(&id, &strict);
+        assert!(r.valid);
+        assert!(r.failures.is_empty());
+        assert_eq!(r.ledger, 10);
+
+        // Age 15 > 5, 5 ledgers left < 10; wrong schema, attester and subject.
+        set_sequence(&env, 25);
+        assert!(client.verify_with_policy(&id, &open).valid);
+        assert_eq!(
+            client.verify_with_policy(&id, &strict).failures,
+            soroban_sdk::vec![&env, PolicyFailure::TooOld, PolicyFailure::ExpiresTooSoon]
+        );
+        let mismatched = VerifyPolicy {
+            schema_id: Some(BytesN::from_array(&env, &[42u8; 32])),
+            attesters: soroban_sdk::vec![&env, stranger.clone()],
+            subject: Some(stranger.clone()),
+            ..open.clone()
+        };
+        assert_eq!(
+            client.verify_with_policy(&id, &mismatched).failures,
+            soroban_sdk::vec![
+                &env,
+                PolicyFailure::WrongSchema,
+                PolicyFailure::UntrustedAttester,
+                PolicyFailure::WrongSubject
+            ]
+        );
+
+        client.revoke_by(&attester, &id);
+        set_sequence(&env, 30);
+        let r = client.verify_with_policy(&id, &open);
+        assert!(!r.valid);
+        assert_eq!(
+            r.failures,
+            soroban_sdk::vec![&env, PolicyFailure::Revoked, PolicyFailure::Expired]
+        );
+
+        // Without expiration there is no minimum remaining validity to miss.
+        let forever = client.attest(
+            &attester, &schema_id, &subject, &data_hash, &None, &None, &2u64,
+        );
+        let lasting = VerifyPolicy {
+            min_remaining_validity: Some(u64::MAX),
+            ..open.clone()
+        };
+        assert!(client.verify_with_policy(&forever, &lasting).valid);
+
+        client.set_requires_subject_consent(&creator, &schema_id, &true);
+        let pending = client.attest(
+            &attester, &schema_id, &subject, &data_hash, &None, &None, &3u64,
+        );
+        assert_eq!(
+            client.verify_with_policy(&pending, &open).failures,
+            soroban_sdk::vec![&env, PolicyFailure::ConsentPending]
+        );
+
+        let unknown = BytesN::from_array(&env, &[43u8; 32]);
+        assert_eq!(
+            client.verify_with_policy(&unknown, &strict).failures,
+            soroban_sdk::vec![&env, PolicyFailure::NotFound]
+        );
+    }
+
+    #[test]
+    fn verify_at_rebuilds_past_status() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[44u8; 32]);
+        let schema_id = client.create_schema_with_config(
+            &creator,
+            &schema_hash,
+            &true,
+            &true,
+            &0u32,
+            &SchemaConfig {
+                renewable: true,
+                ..Default::default()
+            },
+            &None,
+        );
+        let data_hash = BytesN::from_array(&env, &[45u8; 32]);
+        let id = client.attest(
+            &attester,
+            &schema_id,
+            &subject,
+            &data_hash,
+            &None,
+            &Some(30u64),
+            &1u64,
+        );
+        let bulk = client.attest(
+            &attester, &schema_id, &subject, &data_hash, &None, &None, &2u64,
+        );
+
+        set_sequence(&env, 12);
+        assert!(client.try_suspend(&subject, &id).is_err());
+        client.suspend(&attester, &id);
+        assert!(client.verify(&id).unwrap().suspended);
+        assert!(!client.verify(&id).unwrap().valid);
+        assert_eq!(
+            client
+                .verify_with_policy(
+                    &id,
+                    &VerifyPolicy {
+                        schema_id: None,
+                        attesters: Vec::new(&env),
+                        max_age: None,
+                        min_remaining_validity: None,
+                        subject: None,
+                    }
+                )
+                .failures,
+            soroban_sdk::vec![&env, PolicyFailure::Suspended]
+        );
+
+        set_sequence(&env, 14);
+        client.reinstate(&attester, &id);
+        set_sequence(&env, 20);
+        client.renew(&attester, &id, &50u64);
+        set_sequence(&env, 40);
+        client.revoke_by(&attester, &id);
+        assert!(client.try_reinstate(&attester, &id).is_err());
+
+        let kinds: std::vec::Vec<(StatusKind, u64)> = client
+            .get_status_history(&id)
+            .iter()
+            .map(|c| (c.kind, c.ledger))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (StatusKind::Issued, 10),
+                (StatusKind::Suspended, 12),
+                (StatusKind::Reinstated, 14),
+                (StatusKind::Renewed, 20),
+                (StatusKind::Revoked, 40),
+            ]
+        );
+
+        let at = |ledger: u64| client.verify_at(&id, &ledger).unwrap();
+        assert!(!at(9).exists && !at(9).valid);
+        assert!(at(10).valid);
+        assert!(at(12).suspended && !at(13).valid);
+        assert!(at(14).valid);
+        // The renewal at 20 only applies from 20 on.
+        assert_eq!(at(19).expiration, Some(30));
+        assert_eq!(at(35).expiration, Some(50));
+        assert!(at(35).valid);
+        assert!(at(39).valid);
+        assert!(at(40).revoked && !at(40).valid);
+        assert!(client.try_verify_at(&id, &41u64).is_err());
+
+        // Bulk revocation and schema retirement are dated as well.
+        set_sequence(&env, 45);
+        let index = client.get_attestation(&bulk).status_index.unwrap();
+        client.revoke_status_indices(&attester, &soroban_sdk::vec![&env, index]);
+        set_sequence(&env, 48);
+        client.revoke_status_indices(&attester, &soroban_sdk::vec![&env, index]);
+        client.set_schema_status(&creator, &schema_id, &STATUS_RETIRED, &None);
+
+        assert!(client.verify_at(&bulk, &44).unwrap().valid);
+        assert!(client.verify_at(&bulk, &45).unwrap().revoked);
+        assert!(!client.verify_at(&bulk, &47).unwrap().schema_retired);
+        assert!(client.verify_at(&bulk, &48).unwrap().schema_retired);
+        assert_eq!(
+            client.get_status_history(&bulk).last().unwrap(),
+            StatusChange {
+                kind: StatusKind::Revoked,
+                ledger: 45,
+            }
+        );
+
+        let unknown = BytesN::from_array(&env, &[46u8; 32]);
+        assert_eq!(client.verify_at(&unknown, &10), None);
+    }
+
+    #[test]
+    fn subject_consent_flow() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[18u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &false, &0u32);
+        client.set_requires_subject_consent(&creator, &schema_id, &true);
+
+        let data_hash = BytesN::from_array(&env, &[19u8; 32]);
+        let accepted = client.attest(
+            &attester, &schema_id, &subject, &data_hash, &None, &None, &1u64,
+        );
+        let rejected = client.attest(
+            &attester, &schema_id, &subject, &data_hash, &None, &None, &2u64,
+        );
+
+        let vr = client.verify(&accepted).unwrap();
+        assert!(!vr.valid);
+        assert_eq!(vr.consent_status, CONSENT_PENDING);
+
+        // Only the subject decides.
+        set_sequence(&env, 11);
+        assert!(client.try_accept_attestation(&attester, &accepted).is_err());
+        client.accept_attestation(&subject, &accepted);
+        client.reject_attestation(&subject, &rejected);
+
+        let vr = client.verify(&accepted).unwrap();
+        assert!(vr.valid);
+        assert_eq!(vr.consent_status, CONSENT_ACCEPTED);
+
+        let vr = client.verify(&rejected).unwrap();
+        assert!(!vr.valid);
+        assert_eq!(vr.consent_status, CONSENT_REJECTED);
+
+        // Decisions are final.
+        assert!(client.try_accept_attestation(&subject, &rejected).is_err());
+
+        let before = client.verify_at(&accepted, &10).unwrap();
+        assert_eq!(before.consent_status, CONSENT_PENDING);
+        assert!(!before.valid);
+        assert!(client.verify_at(&accepted, &11).unwrap().valid);
+        assert_eq!(
+            client.verify_at(&rejected, &11).unwrap().consent_status,
+            CONSENT_REJECTED
+        );
+    }
+
+    #[test]
+    fn subject_can_renounce() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[20u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &false, &0u32);
+
+        let data_hash = BytesN::from_array(&env, &[21u8; 32]);
+        let att_id = client.attest(
+            &attester, &schema_id, &subject, &data_hash, &None, &None, &1u64,
+        );
+
+        // Disabled by default.
+        assert!(client.try_renounce(&subject, &att_id).is_err());
+
+        client.set_subject_can_renounce(&creator, &schema_id, &true);
+        assert!(client.try_renounce(&attester, &att_id).is_err());
+        client.renounce(&subject, &att_id);
+        client.renounce(&subject, &att_id);
+
+        let vr = client.verify(&att_id).unwrap();
+        assert!(vr.renounced);
+        assert!(!vr.revoked);
+        assert!(!vr.valid);
+    }
+
+    #[test]
+    fn threshold_attestation_flow() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signer3 = Address::generate(&env);
+        let outsider = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[22u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &false, &0u32);
+        client.set_multisig(
+            &creator,
+            &schema_id,
+            &Some(MultisigConfig {
+                signers: soroban_sdk::vec![&env, signer1.clone(), signer2.clone(), signer3.clone()],
+                threshold: 2,
+            }),
+        );
+
+        let data_hash = BytesN::from_array(&env, &[23u8; 32]);
+        // Single-party attest is not enough for threshold schemas.
+        assert!(client
+            .try_attest(&signer1, &schema_id, &subject, &data_hash, &None, &None, &1u64)
+            .is_err());
+        assert!(client
+            .try_propose_attestation(
+                &outsider, &schema_id, &subject, &data_hash, &None, &None, &1u64
+            )
+            .is_err());
+
+        let att_id = client.propose_attestation(
+            &signer1, &schema_id, &subject, &data_hash, &None, &None, &1u64,
+        );
+        let vr = client.verify(&att_id).unwrap();
+        assert!(vr.pending_signatures);
+        assert!(!vr.valid);
+        assert_eq!(vr.signers, soroban_sdk::vec![&env, signer1.clone()]);
+
+        assert!(client.try_cosign(&signer1, &att_id).is_err());
+        assert!(client.try_cosign(&outsider, &att_id).is_err());
+        client.cosign(&signer3, &att_id);
+
+        let vr = client.verify(&att_id).unwrap();
+        assert!(!vr.pending_signatures);
+        assert!(vr.valid);
+        assert_eq!(vr.signers, soroban_sdk::vec![&env, signer1, signer3]);
+
+        // Nothing left to sign.
+        assert!(client.try_cosign(&signer2, &att_id).is_err());
+    }
+
+    #[test]
+    fn removed_signers_do_not_count() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signer3 = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[47u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &false, &0u32);
+        let config = |signers: Vec<Address>| {
+            Some(MultisigConfig {
+                signers,
+                threshold: 2,
+            })
+        };
+        client.set_multisig(
+            &creator,
+            &schema_id,
+            &config(soroban_sdk::vec![
+                &env,
+                signer1.clone(),
+                signer2.clone(),
+                signer3.clone()
+            ]),
+        );
+
+        let data_hash = BytesN::from_array(&env, &[48u8; 32]);
+        let att_id = client.propose_attestation(
+            &signer1, &schema_id, &subject, &data_hash, &None, &None, &1u64,
+        );
+
+        // signer1's proposal no longer counts once they are removed.
+        client.set_multisig(
+            &creator,
+            &schema_id,
+            &config(soroban_sdk::vec![&env, signer2.clone(), signer3.clone()]),
+        );
+        client.cosign(&signer2, &att_id);
+        assert!(client.verify(&att_id).unwrap().pending_signatures);
+
+        client.cosign(&signer3, &att_id);
+        let vr = client.verify(&att_id).unwrap();
+        assert!(!vr.pending_signatures);
+        assert!(vr.valid);
+    }
+
+    #[test]
+    fn single_signer_threshold_proposes_before_attesting() {
+        use soroban_sdk::testutils::Events as _;
+        use soroban_sdk::xdr::{ContractEventBody, ScVal};
+
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let signer = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[49u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &false, &0u32);
+        client.set_multisig(
+            &creator,
+            &schema_id,
+            &Some(MultisigConfig {
+                signers: soroban_sdk::vec![&env, signer.clone()],
+                threshold: 1,
+            }),
+        );
+
+        let data_hash = BytesN::from_array(&env, &[50u8; 32]);
+        let att_id = client.propose_attestation(
+            &signer, &schema_id, &subject, &data_hash, &None, &None, &1u64,
+        );
+
+        let names: std::vec::Vec<std::string::String> = env
+            .events()
+            .all()
+            .filter_by_contract(&contract_id)
+            .events()
+            .iter()
+            .map(|e| {
+                let ContractEventBody::V0(body) = &e.body;
+                match &body.topics[0] {
+                    ScVal::Symbol(s) => s.0.to_utf8_string_lossy(),
+                    other => panic!("unexpected topic {other:?}"),
+                }
+            })
+            .collect();
+        let proposed = names.iter().position(|n| n == "AttestationProposed");
+        let attested = names.iter().position(|n| n == "attested");
+        assert!(proposed.is_some() && attested.is_some(), "{names:?}");
+        assert!(proposed < attested, "{names:?}");
+        assert!(client.verify(&att_id).unwrap().valid);
+    }
+
+    #[test]
+    fn proposer_can_cancel_after_multisig_is_cleared() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[51u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &false, &0u32);
+        client.set_multisig(
+            &creator,
+            &schema_id,
+            &Some(MultisigConfig {
+                signers: soroban_sdk::vec![&env, signer1.clone(), signer2.clone()],
+                threshold: 2,
+            }),
+        );
+
+        let data_hash = BytesN::from_array(&env, &[52u8; 32]);
+        let att_id = client.propose_attestation(
+            &signer1, &schema_id, &subject, &data_hash, &None, &None, &1u64,
+        );
 
-    // Anti-replay helper
-    pub fn get_nonce(env: Env, attester: Address) -> u64 {
-        env.storage()
-            .persistent()
-            .get(&DataKey::Nonce(attester))
-            .unwrap_or(0)
-    }
+        // Without a multisig config the proposal can never be cosigned.
+        client.set_multisig(&creator, &schema_id, &None);
+        assert!(client.try_cosign(&signer2, &att_id).is_err());
 
-    // RF.C.02
-    pub fn attest(
-        env: Env,
-        attester: Address,
-        schema_id: BytesN<32>,
-        subject: Address,
-        data_hash: BytesN<32>,
-        expiration: Option<u64>,
-        nonce: u64,
-    ) -> BytesN<32> {
-        attester.require_auth();
-        let schema = require_schema_exists(&env, &schema_id);
+        // Only the proposer can withdraw it.
+        assert!(client.try_cancel_proposal(&signer2, &att_id).is_err());
+        client.cancel_proposal(&signer1, &att_id);
+        assert!(client.verify(&att_id).is_none());
+        assert!(client.try_cancel_proposal(&signer1, &att_id).is_err());
 
-        if expiration.is_some() && !schema.expires_allowed {
-            panic!("expiration_not_allowed");
-        }
+        // Issued attestations are revoked, not cancelled.
+        let issued = client.attest(
+            &signer1, &schema_id, &subject, &data_hash, &None, &None, &2u64,
+        );
+        assert!(client.try_cancel_proposal(&signer1, &issued).is_err());
+    }
 
-        if schema.attester_mode == MODE_ISSUER_ONLY && attester != schema.creator {
-            panic!("issuer_only");
-        }
+    #[test]
+    fn salted_commitment_disclosure() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
 
-        // Monotonic nonce per attester.
-        let nonce_key = DataKey::Nonce(attester.clone());
-        let current_nonce: u64 = env.storage().persistent().get(&nonce_key).unwrap_or(0);
-        if nonce != current_nonce.saturating_add(1) {
-            panic!("bad_nonce");
-        }
-        env.storage().persistent().set(&nonce_key, &nonce);
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
 
-        let attestation_id = next_attestation_id(&env);
-        let key = DataKey::Attestation(attestation_id.clone());
+        let schema_hash = BytesN::from_array(&env, &[24u8; 32]);
+        let schema_id = client.create_schema_with_config(
+            &creator,
+            &schema_hash,
+            &true,
+            &false,
+            &0u32,
+            &SchemaConfig {
+                salted_commitments: true,
+                ..Default::default()
+            },
+            &None,
+        );
+        // A commitment is not also a Merkle root.
+        assert!(client
+            .try_create_schema_with_config(
+                &creator,
+                &BytesN::from_array(&env, &[26u8; 32]),
+                &true,
+                &false,
+                &0u32,
+                &SchemaConfig {
+                    salted_commitments: true,
+                    merkle_claims: true,
+                    ..Default::default()
+                },
+                &None,
+            )
+            .is_err());
 
-        let timestamp = now_ledger(&env);
-        let attestation = Attestation {
-            schema_id: schema_id.clone(),
-            attester: attester.clone(),
-            subject: subject.clone(),
-            data_hash: data_hash.clone(),
-            timestamp,
-            expiration,
-            revoked: false,
-        };
-        env.storage().persistent().set(&key, &attestation);
+        let claim = Bytes::from_slice(&env, b"birth_date=1990-01-31");
+        let salt = BytesN::from_array(&env, &[0xabu8; 32]);
+        let mut msg = Bytes::from_array(&env, &salt.to_array());
+        msg.append(&claim);
+        let commitment: BytesN<32> = env.crypto().sha256(&msg).into();
 
-        // Event: Attested(attestation_id, schema_id, attester, subject, data_hash, timestamp, expiration)
-        emit_attested(
-            &env,
-            &attestation_id,
-            &schema_id,
+        let att_id = client.attest(
             &attester,
+            &schema_id,
             &subject,
-            &data_hash,
-            timestamp,
-            attestation.expiration,
+            &commitment,
+            &None,
+            &None,
+            &1u64,
         );
 
-        attestation_id
+        assert!(client.verify_claim(&att_id, &claim, &salt));
+        let wrong_claim = Bytes::from_slice(&env, b"birth_date=1990-01-30");
+        assert!(!client.verify_claim(&att_id, &wrong_claim, &salt));
+        let wrong_salt = BytesN::from_array(&env, &[0u8; 32]);
+        assert!(!client.verify_claim(&att_id, &claim, &wrong_salt));
     }
 
-    // RF.C.03
-    pub fn revoke_by(env: Env, revoker: Address, attestation_id: BytesN<32>) {
-        revoker.require_auth();
-        let mut att = require_attestation_exists(&env, &attestation_id);
+    fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+        let (a, b) = if a.to_array() <= b.to_array() {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        let mut msg = Bytes::from_array(env, &[0x01]);
+        msg.append(&Bytes::from_array(env, &a.to_array()));
+        msg.append(&Bytes::from_array(env, &b.to_array()));
+        env.crypto().sha256(&msg).into()
+    }
 
-        if revoker != att.attester {
-            panic!("not_attester");
-        }
+    fn leaf(env: &Env, field: &[u8]) -> BytesN<32> {
+        let mut msg = Bytes::from_array(env, &[0x00]);
+        msg.append(&Bytes::from_slice(env, field));
+        env.crypto().sha256(&msg).into()
+    }
 
-        let schema = require_schema_exists(&env, &att.schema_id);
-        if !schema.revocable {
-            panic!("not_revocable");
-        }
+    #[test]
+    fn merkle_field_disclosure() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
 
-        if att.revoked {
-            return;
-        }
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
 
-        att.revoked = true;
-        env.storage()
-            .persistent()
-            .set(&DataKey::Attestation(attestation_id.clone()), &att);
+        let schema_hash = BytesN::from_array(&env, &[25u8; 32]);
+        let schema_id = client.create_schema_with_config(
+            &creator,
+            &schema_hash,
+            &true,
+            &false,
+            &0u32,
+            &SchemaConfig {
+                merkle_claims: true,
+                ..Default::default()
+            },
+            &None,
+        );
 
-        // Event: Revoked(attestation_id, revoker, timestamp)
-        emit_revoked(&env, &attestation_id, &revoker);
-    }
+        // Four fields, two levels.
+        let f0 = Bytes::from_slice(&env, b"salt0:over_18=true");
+        let f2 = Bytes::from_slice(&env, b"salt2:name=Maria");
+        let f3 = Bytes::from_slice(&env, b"salt3:document=12345678900");
+        let l0 = leaf(&env, b"salt0:over_18=true");
+        let l1 = leaf(&env, b"salt1:nationality=BR");
+        let l2 = leaf(&env, b"salt2:name=Maria");
+        let l3 = leaf(&env, b"salt3:document=12345678900");
+        let n01 = hash_pair(&env, &l0, &l1);
+        let n23 = hash_pair(&env, &l2, &l3);
+        let root = hash_pair(&env, &n01, &n23);
 
-    // RF.C.04
-    pub fn verify(env: Env, attestation_id: BytesN<32>) -> Option<VerifyResult> {
-        let att: Option<Attestation> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Attestation(attestation_id));
+        let att_id = client.attest(&attester, &schema_id, &subject, &root, &None, &None, &1u64);
 
-        let att = att?;
+        let proof = soroban_sdk::vec![&env, l1.clone(), n23.clone()];
+        assert!(client.verify_field(&att_id, &f0, &proof));
 
-        let now = now_ledger(&env);
-        let expired = match att.expiration {
-            Some(exp) => now >= exp,
-            None => false,
-        };
-        let revoked = att.revoked;
-        let valid = !revoked && !expired;
+        let proof = soroban_sdk::vec![&env, l2.clone(), n01.clone()];
+        assert!(client.verify_field(&att_id, &f3, &proof));
 
-        Some(VerifyResult {
-            exists: true,
-            valid,
-            revoked,
-            expired,
-            schema_id: att.schema_id,
-            attester: att.attester,
-            subject: att.subject,
-            data_hash: att.data_hash,
-            timestamp: att.timestamp,
-            expiration: att.expiration,
-        })
-    }
+        // Wrong field or wrong sibling must fail.
+        let proof = soroban_sdk::vec![&env, l1.clone(), n23.clone()];
+        assert!(!client.verify_field(&att_id, &f2, &proof));
+        let proof = soroban_sdk::vec![&env, l2.clone(), n23.clone()];
+        assert!(!client.verify_field(&att_id, &f0, &proof));
 
-    pub fn get_schema(env: Env, schema_id: BytesN<32>) -> Schema {
-        require_schema_exists(&env, &schema_id)
+        // An inner node's preimage is not a field.
+        let (a, b) = if l0.to_array() <= l1.to_array() {
+            (l0, l1)
+        } else {
+            (l1, l0)
+        };
+        let mut node_preimage = Bytes::from_array(&env, &a.to_array());
+        node_preimage.append(&Bytes::from_array(&env, &b.to_array()));
+        let proof = soroban_sdk::vec![&env, n23];
+        assert!(!client.verify_field(&att_id, &node_preimage, &proof));
     }
 
-    pub fn get_attestation(env: Env, attestation_id: BytesN<32>) -> Attestation {
-        require_attestation_exists(&env, &attestation_id)
+    fn ordered_pair(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+        let mut msg = Bytes::from_array(env, &[0x01]);
+        msg.append(&Bytes::from_array(env, &left.to_array()));
+        msg.append(&Bytes::from_array(env, &right.to_array()));
+        env.crypto().sha256(&msg).into()
     }
 
-    pub fn version(env: Env) -> Symbol {
-        Symbol::new(&env, "v0.1")
-    }
-}
+    #[test]
+    fn merkle_batch_anchoring_and_leaf_revocation() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::EnvTestConfig;
-    use soroban_sdk::testutils::{Address as _, Ledger as _, LedgerInfo};
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let schema_hash = BytesN::from_array(&env, &[26u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &true, &0u32);
+
+        let leaves: std::vec::Vec<BatchLeaf> = (0..4u32)
+            .map(|i| BatchLeaf {
+                index: i,
+                subject: Address::generate(&env),
+                data_hash: BytesN::from_array(&env, &[i as u8 + 1; 32]),
+            })
+            .collect();
+        let h: std::vec::Vec<BytesN<32>> =
+            leaves.iter().map(|l| batch_leaf_hash(&env, l)).collect();
+        let mut leaf_preimage = Bytes::from_array(&env, &[0x00, 0, 0, 0, 1]);
+        leaf_preimage.append(&leaves[1].subject.clone().to_xdr(&env));
+        leaf_preimage.append(&Bytes::from_array(&env, &[2u8; 32]));
+        assert_eq!(h[1], BytesN::from(env.crypto().sha256(&leaf_preimage)));
+        let n01 = ordered_pair(&env, &h[0], &h[1]);
+        let n23 = ordered_pair(&env, &h[2], &h[3]);
+        let root = ordered_pair(&env, &n01, &n23);
+
+        let root_id = client.attest_merkle_root(&attester, &schema_id, &root, &4u32, &Some(100u64));
+        assert_eq!(client.get_batch(&root_id).count, 4);
+
+        // Batch ids live in their own domain, never in the attestation one.
+        let mut preimage = Bytes::from_slice(&env, b"eas-soroban:batch");
+        let mut counter = [0u8; 32];
+        counter[31] = 1;
+        preimage.append(&Bytes::from_array(&env, &counter));
+        let expected: BytesN<32> = env.crypto().sha256(&preimage).into();
+        assert_eq!(root_id, expected);
+        assert_eq!(client.verify(&root_id), None);
+        assert!(client.try_revoke_by(&attester, &root_id).is_err());
+
+        let proof_of = |i: usize| match i {
+            0 => soroban_sdk::vec![&env, h[1].clone(), n23.clone()],
+            1 => soroban_sdk::vec![&env, h[0].clone(), n23.clone()],
+            2 => soroban_sdk::vec![&env, h[3].clone(), n01.clone()],
+            _ => soroban_sdk::vec![&env, h[2].clone(), n01.clone()],
+        };
+        for (i, leaf) in leaves.iter().enumerate() {
+            assert!(client.verify_in_batch(&root_id, leaf, &proof_of(i)));
+        }
+
+        // A leaf cannot be presented under another index.
+        let mut moved = leaves[2].clone();
+        moved.index = 0;
+        assert!(!client.verify_in_batch(&root_id, &moved, &proof_of(2)));
+
+        client.revoke_batch_leaves(&attester, &root_id, &soroban_sdk::vec![&env, 2u32]);
+        assert!(!client.verify_in_batch(&root_id, &leaves[2], &proof_of(2)));
+        assert!(client.verify_in_batch(&root_id, &leaves[3], &proof_of(3)));
+
+        set_sequence(&env, 100);
+        assert!(!client.verify_in_batch(&root_id, &leaves[3], &proof_of(3)));
+        set_sequence(&env, 10);
+
+        client.revoke_batch(&attester, &root_id);
+        assert!(!client.verify_in_batch(&root_id, &leaves[0], &proof_of(0)));
+    }
 
     #[test]
-    fn schema_attest_verify_revoke() {
-        let mut env = Env::default();
-        env.set_config(EnvTestConfig {
-            capture_snapshot_at_drop: false,
-        });
-        env.mock_all_auths();
-        env.ledger().set(LedgerInfo {
-            timestamp: 0,
-            protocol_version: 25,
-            sequence_number: 1,
-            network_id: [0; 32],
-            base_reserve: 10,
-            min_temp_entry_ttl: 1,
-            min_persistent_entry_ttl: 1,
-            max_entry_ttl: 100,
+    fn bitmap_spans_chunks() {
+        let env = setup_env(10);
+        let contract_id = env.register(EasContract, ());
+        let root_id = BytesN::from_array(&env, &[27u8; 32]);
+
+        env.as_contract(&contract_id, || {
+            let key = |chunk| DataKey::BatchRevocations(root_id.clone(), chunk);
+            let indices = soroban_sdk::vec![
+                &env,
+                0u32,
+                7,
+                BITMAP_CHUNK_BITS - 1,
+                BITMAP_CHUNK_BITS + 9,
+                3
+            ];
+            bitmap_set(&env, key, &indices);
+
+            for i in indices.iter() {
+                assert!(bitmap_get(&env, key, i));
+            }
+            for i in [
+                1u32,
+                6,
+                8,
+                BITMAP_CHUNK_BITS,
+                BITMAP_CHUNK_BITS + 8,
+                5 * BITMAP_CHUNK_BITS,
+            ] {
+                assert!(!bitmap_get(&env, key, i));
+            }
         });
+    }
+
+    #[test]
+    fn status_list_bulk_revocation() {
+        use soroban_sdk::testutils::Events as _;
 
+        let env = setup_env(10);
         let creator = Address::generate(&env);
         let attester = Address::generate(&env);
         let subject = Address::generate(&env);
@@ -361,35 +3870,118 @@ mod test {
         let contract_id = env.register(EasContract, ());
         let client = EasContractClient::new(&env, &contract_id);
 
-        let schema_hash = BytesN::from_array(&env, &[7u8; 32]);
-        let schema_id = client.create_schema(&creator, &schema_hash, &true, &false, &0u32);
-        assert_eq!(schema_id, schema_hash);
+        let revocable_hash = BytesN::from_array(&env, &[28u8; 32]);
+        let revocable_id = client.create_schema(&creator, &revocable_hash, &true, &false, &0u32);
+        let permanent_hash = BytesN::from_array(&env, &[29u8; 32]);
+        let permanent_id = client.create_schema(&creator, &permanent_hash, &false, &false, &0u32);
 
-        let data_hash = BytesN::from_array(&env, &[9u8; 32]);
-        let att_id = client.attest(&attester, &schema_id, &subject, &data_hash, &None, &1u64);
+        let data_hash = BytesN::from_array(&env, &[30u8; 32]);
+        let mut ids = std::vec::Vec::new();
+        for nonce in 1..=3u64 {
+            ids.push(client.attest(
+                &attester,
+                &revocable_id,
+                &subject,
+                &data_hash,
+                &None,
+                &None,
+                &nonce,
+            ));
+        }
+        let permanent = client.attest(
+            &attester,
+            &permanent_id,
+            &subject,
+            &data_hash,
+            &None,
+            &None,
+            &4u64,
+        );
 
-        let vr = client.verify(&att_id).unwrap();
-        assert!(vr.exists);
-        assert!(vr.valid);
-        assert!(!vr.revoked);
-        assert!(!vr.expired);
+        assert_eq!(client.get_attestation(&ids[0]).status_index, Some(0));
+        assert_eq!(client.get_attestation(&ids[2]).status_index, Some(2));
+        assert_eq!(client.get_attestation(&permanent).status_index, None);
 
-        client.revoke_by(&attester, &att_id);
-        let vr2 = client.verify(&att_id).unwrap();
-        assert!(vr2.exists);
-        assert!(!vr2.valid);
-        assert!(vr2.revoked);
+        client.revoke_status_indices(&attester, &soroban_sdk::vec![&env, 0u32, 2]);
+        assert!(client.verify(&ids[0]).unwrap().revoked);
+        assert!(client.verify(&ids[1]).unwrap().valid);
+        assert!(client.verify(&ids[2]).unwrap().revoked);
+        assert!(client.verify(&permanent).unwrap().valid);
+
+        // Bit 0 and bit 2, MSB first.
+        assert_eq!(
+            client.get_status_list_chunk(&attester, &0u32),
+            Bytes::from_array(&env, &[0b1010_0000])
+        );
+
+        assert!(client
+            .try_revoke_status_indices(&attester, &soroban_sdk::vec![&env, 3u32])
+            .is_err());
+
+        // Already revoked through the list: no second Revoked entry or event.
+        set_sequence(&env, 11);
+        client.revoke_by(&attester, &ids[0]);
+        assert_eq!(
+            env.events()
+                .all()
+                .filter_by_contract(&contract_id)
+                .events()
+                .len(),
+            0
+        );
+        assert_eq!(
+            client.get_status_history(&ids[0]),
+            soroban_sdk::vec![
+                &env,
+                StatusChange {
+                    kind: StatusKind::Issued,
+                    ledger: 10,
+                },
+                StatusChange {
+                    kind: StatusKind::Revoked,
+                    ledger: 10,
+                },
+            ]
+        );
     }
 
     #[test]
-    #[should_panic(expected = "issuer_only")]
-    fn issuer_only_enforced() {
-        let mut env = Env::default();
-        env.set_config(EnvTestConfig {
-            capture_snapshot_at_drop: false,
-        });
-        env.mock_all_auths();
+    fn offchain_timestamps_and_revocations() {
+        let env = setup_env(10);
+        let signer = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let a = BytesN::from_array(&env, &[31u8; 32]);
+        let b = BytesN::from_array(&env, &[32u8; 32]);
+        assert_eq!(client.get_timestamp(&a), None);
+
+        let first = client.timestamp(&a);
+        assert_eq!(first.ledger, 10);
+
+        // First-seen is kept when the hash is submitted again.
+        set_sequence(&env, 20);
+        let records = client.multi_timestamp(&soroban_sdk::vec![&env, a.clone(), b.clone()]);
+        assert_eq!(records.get(0).unwrap(), first);
+        assert_eq!(records.get(1).unwrap().ledger, 20);
+        assert_eq!(client.get_timestamp(&b).unwrap().ledger, 20);
+
+        client.revoke_offchain(&signer, &a);
+        assert_eq!(
+            client.get_offchain_revocation(&signer, &a).unwrap().ledger,
+            20
+        );
+        assert_eq!(client.get_offchain_revocation(&other, &a), None);
+    }
 
+    #[test]
+    fn v2_events_carry_indexed_topics() {
+        use soroban_sdk::testutils::Events as _;
+        use soroban_sdk::{Event as _, TryFromVal};
+
+        let env = setup_env(10);
         let creator = Address::generate(&env);
         let attester = Address::generate(&env);
         let subject = Address::generate(&env);
@@ -397,22 +3989,94 @@ mod test {
         let contract_id = env.register(EasContract, ());
         let client = EasContractClient::new(&env, &contract_id);
 
-        let schema_hash = BytesN::from_array(&env, &[1u8; 32]);
-        let schema_id = client.create_schema(&creator, &schema_hash, &true, &false, &1u32);
+        let schema_hash = BytesN::from_array(&env, &[16u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &false, &0u32);
+        // The legacy tuple, when enabled, shares the operation's sequence number.
+        let per_op = if cfg!(feature = "legacy-events") {
+            2
+        } else {
+            1
+        };
 
-        let data_hash = BytesN::from_array(&env, &[2u8; 32]);
-        client.attest(&attester, &schema_id, &subject, &data_hash, &None, &1u64);
+        let expected = SchemaCreated {
+            schema_id: schema_id.clone(),
+            creator: creator.clone(),
+            schema_uri_hash: schema_hash.clone(),
+            revocable: true,
+            expires_allowed: false,
+            attester_mode: 0,
+            ledger: 10,
+            seq: 1,
+        };
+        let events = env.events().all().filter_by_contract(&contract_id);
+        assert!(events
+            .events()
+            .contains(&expected.to_xdr(&env, &contract_id)));
+        assert_eq!(events.events().len(), per_op);
+        #[cfg(feature = "legacy-events")]
+        {
+            use soroban_sdk::xdr::{ContractEventBody, ScVal};
+            // Legacy topic arity is unchanged; seq rides at the end of the data.
+            let ContractEventBody::V0(body) = &events.events()[0].body;
+            assert_eq!(body.topics.len(), 1);
+            let ScVal::Vec(Some(data)) = &body.data else {
+                panic!("legacy data is a tuple");
+            };
+            assert_eq!(data.len(), 8);
+            assert_eq!(data.last(), Some(&ScVal::U64(1)));
+        }
+
+        let data_hash = BytesN::from_array(&env, &[17u8; 32]);
+        let att_id = client.attest(
+            &attester, &schema_id, &subject, &data_hash, &None, &None, &1u64,
+        );
+        let expected = Attested {
+            schema_id: schema_id.clone(),
+            attester: attester.clone(),
+            subject: subject.clone(),
+            attestation_id: att_id.clone(),
+            data_hash,
+            timestamp: 10,
+            expiration: None,
+            status_index: Some(0),
+            seq: 2,
+        };
+        let events = env.events().all().filter_by_contract(&contract_id);
+        assert!(events
+            .events()
+            .contains(&expected.to_xdr(&env, &contract_id)));
+
+        // Topics are ["attested", schema_id, attester, subject].
+        let topics = expected.topics(&env);
+        assert_eq!(topics.len(), 4);
+        assert_eq!(
+            Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(),
+            Symbol::new(&env, "attested")
+        );
+
+        set_sequence(&env, 12);
+        client.revoke_by(&attester, &att_id);
+        let expected = Revoked {
+            schema_id,
+            attester: attester.clone(),
+            subject,
+            attestation_id: att_id,
+            revoker: attester,
+            ledger: 12,
+            seq: 3,
+        };
+        let events = env.events().all().filter_by_contract(&contract_id);
+        assert!(events
+            .events()
+            .contains(&expected.to_xdr(&env, &contract_id)));
     }
 
     #[test]
-    #[should_panic(expected = "bad_nonce")]
-    fn bad_nonce_rejected() {
-        let mut env = Env::default();
-        env.set_config(EnvTestConfig {
-            capture_snapshot_at_drop: false,
-        });
-        env.mock_all_auths();
+    fn state_checkpoint_replays_event_stream() {
+        use soroban_sdk::testutils::Events as _;
+        use soroban_sdk::xdr::{Limits, ScVal, ScVec, WriteXdr};
 
+        let env = setup_env(10);
         let creator = Address::generate(&env);
         let attester = Address::generate(&env);
         let subject = Address::generate(&env);
@@ -420,14 +4084,103 @@ mod test {
         let contract_id = env.register(EasContract, ());
         let client = EasContractClient::new(&env, &contract_id);
 
-        let schema_hash = BytesN::from_array(&env, &[3u8; 32]);
+        let genesis = client.get_state_checkpoint();
+        assert_eq!(genesis.seq, 0);
+        assert_eq!(genesis.hash, BytesN::from_array(&env, &[0u8; 32]));
+
+        // Fold sha256(prev || xdr([topics, data])) over what an indexer sees.
+        let mut seen = 0u64;
+        let mut hash = genesis.hash;
+        let mut replay = |env: &Env| {
+            for e in env.events().all().filter_by_contract(&contract_id).events() {
+                let soroban_sdk::xdr::ContractEventBody::V0(body) = &e.body;
+                let payload = ScVal::Vec(Some(ScVec(
+                    std::vec![
+                        ScVal::Vec(Some(ScVec(body.topics.clone()))),
+                        body.data.clone()
+                    ]
+                    .try_into()
+                    .unwrap(),
+                )));
+                let mut buf = Bytes::from_array(env, &hash.to_array());
+                buf.extend_from_slice(&WriteXdr::to_xdr(&payload, Limits::none()).unwrap());
+                hash = env.crypto().sha256(&buf).into();
+            }
+            // One sequence number per operation, however many events it published.
+            seen += 1;
+            let cp = client.get_state_checkpoint();
+            assert_eq!(cp.seq, seen);
+            assert_eq!(cp.hash, hash);
+        };
+
+        let schema_hash = BytesN::from_array(&env, &[18u8; 32]);
         let schema_id = client.create_schema(&creator, &schema_hash, &true, &false, &0u32);
+        replay(&env);
 
-        let data_hash = BytesN::from_array(&env, &[4u8; 32]);
-        // First nonce must be 1.
-        client.attest(&attester, &schema_id, &subject, &data_hash, &None, &2u64);
+        let data_hash = BytesN::from_array(&env, &[19u8; 32]);
+        let att_id = client.attest(
+            &attester, &schema_id, &subject, &data_hash, &None, &None, &1u64,
+        );
+        replay(&env);
+
+        client.set_subject_can_renounce(&creator, &schema_id, &true);
+        replay(&env);
+
+        // Every state change advances the chain, setters included.
+        let before = client.get_state_checkpoint().seq;
+        client.set_fee_payer(&attester, &Some(creator.clone()));
+        replay(&env);
+        assert_eq!(client.get_state_checkpoint().seq, before + 1);
+
+        set_sequence(&env, 11);
+        client.revoke_by(&attester, &att_id);
+        replay(&env);
+
+        let cp = client.get_state_checkpoint();
+        assert_eq!(cp.ledger, 11);
+        assert_eq!(cp.seq, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid_metadata_name")]
+    fn schema_metadata_size_limits() {
+        let env = setup_env(10);
+        let creator = Address::generate(&env);
+
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+
+        let metadata = SchemaMetadata {
+            name: String::from_bytes(&env, &[b'a'; 65]),
+            description: String::from_str(&env, ""),
+            version: SchemaVersion {
+                major: 1,
+                minor: 0,
+                patch: 0,
+            },
+            tags: Vec::new(&env),
+        };
+
+        let schema_hash = BytesN::from_array(&env, &[15u8; 32]);
+        client.create_schema_with_config(
+            &creator,
+            &schema_hash,
+            &true,
+            &false,
+            &0u32,
+            &SchemaConfig::default(),
+            &Some(metadata),
+        );
     }
 }
 
+pub mod encoder;
+
 #[cfg(test)]
 mod security_tests;
+
+#[cfg(feature = "vc")]
+pub mod vc;
+
+#[cfg(feature = "client")]
+pub mod client;
@@ -70,7 +70,7 @@ proptest! {
 
         for i in 1..=n {
             let data_hash = bytes32_from_u8(&env, i);
-            client.attest(&attester, &schema_id, &subject, &data_hash, &None, &(i as u64));
+            client.attest(&attester, &schema_id, &subject, &data_hash, &None, &None, &(i as u64));
         }
 
         let last = client.get_nonce(&attester);
@@ -93,11 +93,11 @@ proptest! {
 
         for i in 1..=n1 {
             let data_hash = bytes32_from_u8(&env, i);
-            client.attest(&attester1, &schema_id, &subject, &data_hash, &None, &(i as u64));
+            client.attest(&attester1, &schema_id, &subject, &data_hash, &None, &None, &(i as u64));
         }
         for i in 1..=n2 {
             let data_hash = bytes32_from_u8(&env, (100u8).wrapping_add(i));
-            client.attest(&attester2, &schema_id, &subject, &data_hash, &None, &(i as u64));
+            client.attest(&attester2, &schema_id, &subject, &data_hash, &None, &None, &(i as u64));
         }
 
         prop_assert_eq!(client.get_nonce(&attester1), n1 as u64);
@@ -119,14 +119,14 @@ proptest! {
 
         for i in 1..=pre {
             let data_hash = bytes32_from_u8(&env, i);
-            client.attest(&attester, &schema_id, &subject, &data_hash, &None, &(i as u64));
+            client.attest(&attester, &schema_id, &subject, &data_hash, &None, &None, &(i as u64));
         }
 
         let before = client.get_nonce(&attester);
         let bad = before + 2;
         assert_panics_with_msg(|| {
             let data_hash = bytes32_from_u8(&env, 200);
-            client.attest(&attester, &schema_id, &subject, &data_hash, &None, &bad);
+            client.attest(&attester, &schema_id, &subject, &data_hash, &None, &None, &bad);
         }, "bad_nonce");
         let after = client.get_nonce(&attester);
         prop_assert_eq!(after, before);
@@ -147,12 +147,12 @@ proptest! {
 
         assert_panics_with_msg(|| {
             let data_hash = bytes32_from_u8(&env, 22);
-            client.attest(&other, &schema_id, &subject, &data_hash, &None, &1u64);
+            client.attest(&other, &schema_id, &subject, &data_hash, &None, &None, &1u64);
         }, "issuer_only");
 
         // Creator can attest.
         let data_hash = bytes32_from_u8(&env, 23);
-        let _ = client.attest(&creator, &schema_id, &subject, &data_hash, &None, &1u64);
+        let _ = client.attest(&creator, &schema_id, &subject, &data_hash, &None, &None, &1u64);
 
         // Some noise (delta doesn't matter, just ensures multiple cases).
         prop_assert!(delta <= 5);
@@ -174,16 +174,17 @@ proptest! {
         let now = env.ledger().sequence();
         let exp = (now as u64) + (delta as u64);
         let data_hash = bytes32_from_u8(&env, 32);
-        let att_id = client.attest(&attester, &schema_id, &subject, &data_hash, &Some(exp), &1u64);
-
-        let vr_now = client.verify(&att_id).unwrap();
         if delta == 0 {
-            prop_assert!(vr_now.expired);
-            prop_assert!(!vr_now.valid);
-        } else {
-            prop_assert!(!vr_now.expired);
-            prop_assert!(vr_now.valid);
+            // Would be expired on arrival, so attest refuses it.
+            let res = client.try_attest(&attester, &schema_id, &subject, &data_hash, &None, &Some(exp), &1u64);
+            prop_assert_eq!(res, Err(Ok(crate::ValidityError::ExpirationInPast.into())));
+            return Ok(());
         }
+        let att_id = client.attest(&attester, &schema_id, &subject, &data_hash, &None, &Some(exp), &1u64);
+
+        let vr_now = client.verify(&att_id).unwrap();
+        prop_assert!(!vr_now.expired);
+        prop_assert!(vr_now.valid);
 
         // At now == exp, we consider it expired (>=).
         env.ledger().set(LedgerInfo {
@@ -216,7 +217,7 @@ proptest! {
 
         assert_panics_with_msg(|| {
             let data_hash = bytes32_from_u8(&env, 42);
-            client.attest(&attester, &schema_id, &subject, &data_hash, &Some(exp), &1u64);
+            client.attest(&attester, &schema_id, &subject, &data_hash, &None, &Some(exp), &1u64);
         }, "expiration_not_allowed");
     }
 }
@@ -247,6 +248,7 @@ fn revoke_rules_and_idempotency() {
         &subject,
         &bytes32_from_u8(&env, 52),
         &None,
+        &None,
         &1u64,
     );
     assert_panics(|| client.revoke_by(&attester, &att_id_nr));
@@ -264,6 +266,7 @@ fn revoke_rules_and_idempotency() {
         &subject,
         &bytes32_from_u8(&env, 62),
         &None,
+        &None,
         &2u64,
     );
 
@@ -285,6 +288,36 @@ fn verify_unknown_is_none() {
     assert!(client.verify(&unknown).is_none());
 }
 
+#[test]
+fn expiration_at_current_ledger_is_refused() {
+    let env = setup_env(10);
+    let creator = Address::generate(&env);
+    let attester = Address::generate(&env);
+    let subject = Address::generate(&env);
+
+    let contract_id = env.register(EasContract, ());
+    let client = EasContractClient::new(&env, &contract_id);
+
+    let schema_hash = bytes32_from_u8(&env, 31);
+    let schema_id =
+        client.create_schema(&creator, &schema_hash, &true, &true, &MODE_PERMISSIONLESS);
+
+    // verify treats now >= exp as expired, so exp == now must not be accepted.
+    let exp = env.ledger().sequence() as u64;
+    let data_hash = bytes32_from_u8(&env, 32);
+    let res = client.try_attest(
+        &attester,
+        &schema_id,
+        &subject,
+        &data_hash,
+        &None,
+        &Some(exp),
+        &1u64,
+    );
+    assert_eq!(res, Err(Ok(crate::ValidityError::ExpirationInPast.into())));
+    assert_eq!(client.get_nonce(&attester), 0);
+}
+
 proptest! {
     // "Fuzz-like" randomized scenario. Keeps it small so it stays fast and stable.
     #![proptest_config(ProptestConfig { cases: 32, .. ProptestConfig::default() })]
@@ -342,7 +375,7 @@ proptest! {
                     let nonce = if want_bad_nonce { current_nonce + 2 } else { current_nonce + 1 };
 
                     let expiration = if (b & 0x20) != 0 {
-                        Some(now + ((b as u64) % 6))
+                        Some(now + 1 + ((b as u64) % 6))
                     } else {
                         None
                     };
@@ -350,12 +383,12 @@ proptest! {
                     let before = client.get_nonce(attester);
                     if want_bad_nonce {
                         assert_panics_with_msg(|| {
-                            let _ = client.attest(attester, &schema_id, &subject, &bytes32_from_u8(&env, b), &expiration, &nonce);
+                            let _ = client.attest(attester, &schema_id, &subject, &bytes32_from_u8(&env, b), &None, &expiration, &nonce);
                         }, "bad_nonce");
                         let after = client.get_nonce(attester);
                         prop_assert_eq!(after, before);
                     } else {
-                        let id = client.attest(attester, &schema_id, &subject, &bytes32_from_u8(&env, b), &expiration, &nonce);
+                        let id = client.attest(attester, &schema_id, &subject, &bytes32_from_u8(&env, b), &None, &expiration, &nonce);
                         ids.push(id);
                         model.push(ModelAtt { attester_idx: which, expiration, revoked: false });
                     }
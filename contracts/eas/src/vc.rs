@@ -0,0 +1,631 @@
+//! Host-side mapping between contract records and W3C Verifiable Credentials
+//! (Data Model 2.0, JSON-LD).
+//!
+//! Issuer and subject are `did:pkh` identifiers built from their Stellar
+//! strkeys. Ledger-based fields have no calendar equivalent on-chain, so they
+//! are carried verbatim in the `easSoroban` extension; consumers must check
+//! live validity through `credentialStatus`, which points back to `verify` on
+//! the contract.
+//!
+//! Our terms are defined in an inline JSON-LD context (no hosted context to
+//! fetch). The status entries are EAS-specific, not W3C Bitstring Status List
+//! entries: there is no status list credential, only the contract's bitmap
+//! (`get_status_list_chunk`).
+
+extern crate std;
+
+use std::format;
+use std::string::String as StdString;
+use std::vec::Vec as StdVec;
+
+use serde_json::{json, Value};
+use soroban_sdk::{Address, BytesN, Env, String};
+
+use crate::{Attestation, Schema, VerifyResult};
+
+const CREDENTIALS_V2_CONTEXT: &str = "https://www.w3.org/ns/credentials/v2";
+const EAS_SOROBAN_VOCAB: &str = "urn:eas-soroban:";
+
+/// Where the credentials live: CAIP-2 network reference (`pubnet`,
+/// `testnet`, ...) and the EAS contract strkey.
+#[derive(Clone, Copy, Debug)]
+pub struct VcContext<'a> {
+    pub network: &'a str,
+    pub contract_id: &'a str,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VcError {
+    MissingField(&'static str),
+    InvalidField(&'static str),
+    /// Well-formed, but issued on another network or by another contract.
+    OtherDeployment(&'static str),
+}
+
+impl core::fmt::Display for VcError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            VcError::MissingField(name) => write!(f, "missing field `{name}`"),
+            VcError::InvalidField(name) => write!(f, "invalid field `{name}`"),
+            VcError::OtherDeployment(name) => {
+                write!(f, "field `{name}` refers to another network or contract")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VcError {}
+
+impl VcContext<'_> {
+    fn contract_uri(&self) -> StdString {
+        format!("stellar:{}:{}", self.network, self.contract_id)
+    }
+
+    /// `did:pkh:stellar:<network>:<strkey>`
+    pub fn did(&self, address: &Address) -> StdString {
+        format!("did:pkh:stellar:{}:{}", self.network, strkey(address))
+    }
+
+    fn attestations_uri(&self) -> StdString {
+        format!("{}/attestations", self.contract_uri())
+    }
+
+    fn schemas_uri(&self) -> StdString {
+        format!("{}/schemas", self.contract_uri())
+    }
+
+    fn attestation_uri(&self, attestation_id: &BytesN<32>) -> StdString {
+        format!("{}/{}", self.attestations_uri(), hex32(attestation_id))
+    }
+
+    fn schema_uri(&self, schema_id: &BytesN<32>) -> StdString {
+        format!("{}/{}", self.schemas_uri(), hex32(schema_id))
+    }
+}
+
+// Inline JSON-LD context for the terms the VC v2 context does not define.
+// Our types scope `@vocab` to their own properties; `easSoroban` is an
+// opaque JSON literal.
+fn eas_context() -> Value {
+    let scoped = |name: &str| {
+        json!({
+            "@id": format!("{EAS_SOROBAN_VOCAB}{name}"),
+            "@context": { "@vocab": EAS_SOROBAN_VOCAB },
+        })
+    };
+    json!({
+        "EasSorobanAttestation": format!("{EAS_SOROBAN_VOCAB}Attestation"),
+        "EasSorobanSchema": scoped("Schema"),
+        "EasSorobanVerify": scoped("Verify"),
+        "EasSorobanStatusListEntry": scoped("StatusListEntry"),
+        "dataHash": format!("{EAS_SOROBAN_VOCAB}dataHash"),
+        "easSoroban": {
+            "@id": format!("{EAS_SOROBAN_VOCAB}extension"),
+            "@type": "@json",
+        },
+    })
+}
+
+fn strkey(address: &Address) -> StdString {
+    let s = address.to_string();
+    let mut buf = std::vec![0u8; s.len() as usize];
+    s.copy_into_slice(&mut buf);
+    StdString::from_utf8(buf).expect("strkeys are ascii")
+}
+
+fn hex32(bytes: &BytesN<32>) -> StdString {
+    hex::encode(bytes.to_array())
+}
+
+fn credential_status(
+    ctx: &VcContext,
+    attestation_id: &BytesN<32>,
+    att_status: Option<(&Address, u32)>,
+) -> Value {
+    let mut entries = StdVec::new();
+    entries.push(json!({
+        "id": ctx.attestation_uri(attestation_id),
+        "type": "EasSorobanVerify",
+        "contract": ctx.contract_id,
+        "method": "verify",
+        "attestationId": hex32(attestation_id),
+    }));
+    // Bit `statusListIndex` of the attester's bitmap, read on-chain; not a
+    // BitstringStatusListEntry (there is no status list credential).
+    if let Some((attester, index)) = att_status {
+        entries.push(json!({
+            "id": format!("{}/status-lists/{}#{}", ctx.contract_uri(), strkey(attester), index),
+            "type": "EasSorobanStatusListEntry",
+            "statusPurpose": "revocation",
+            "contract": ctx.contract_id,
+            "method": "get_status_list_chunk",
+            "attester": ctx.did(attester),
+            "statusListIndex": index,
+        }));
+    }
+    Value::Array(entries)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn base_credential(
+    ctx: &VcContext,
+    attestation_id: &BytesN<32>,
+    schema_id: &BytesN<32>,
+    attester: &Address,
+    subject: &Address,
+    data_hash: &BytesN<32>,
+    status: Value,
+    extension: Value,
+) -> Value {
+    json!({
+        "@context": [CREDENTIALS_V2_CONTEXT, eas_context()],
+        "id": ctx.attestation_uri(attestation_id),
+        "type": ["VerifiableCredential", "EasSorobanAttestation"],
+        "issuer": ctx.did(attester),
+        "credentialSubject": {
+            "id": ctx.did(subject),
+            "dataHash": hex32(data_hash),
+        },
+        "credentialSchema": {
+            "id": ctx.schema_uri(schema_id),
+            "type": "EasSorobanSchema",
+        },
+        "credentialStatus": status,
+        "easSoroban": extension,
+    })
+}
+
+/// Exports a stored attestation as a VC. Lossless: see [`attestation_from_vc`].
+pub fn attestation_to_vc(ctx: &VcContext, attestation_id: &BytesN<32>, att: &Attestation) -> Value {
+    let status = credential_status(
+        ctx,
+        attestation_id,
+        att.status_index.map(|index| (&att.attester, index)),
+    );
+    let extension = json!({
+        "timestamp": att.timestamp,
+        "validFrom": att.valid_from,
+        "expiration": att.expiration,
+        "revoked": att.revoked,
+        "pendingSignatures": att.pending_signatures,
+        "consentStatus": att.consent_status,
+        "renounced": att.renounced,
+        "suspended": att.suspended,
+        "statusIndex": att.status_index,
+    });
+    base_credential(
+        ctx,
+        attestation_id,
+        &att.schema_id,
+        &att.attester,
+        &att.subject,
+        &att.data_hash,
+        status,
+        extension,
+    )
+}
+
+/// Exports the result of `verify` as a VC carrying a snapshot of its status
+/// under `easSoroban.status`.
+pub fn verify_result_to_vc(
+    ctx: &VcContext,
+    attestation_id: &BytesN<32>,
+    vr: &VerifyResult,
+) -> Value {
+    let signers: StdVec<StdString> = vr.signers.iter().map(|s| ctx.did(&s)).collect();
+    let extension = json!({
+        "timestamp": vr.timestamp,
+        "validFrom": vr.valid_from,
+        "expiration": vr.expiration,
+        "status": {
+            "valid": vr.valid,
+            "revoked": vr.revoked,
+            "renounced": vr.renounced,
+            "suspended": vr.suspended,
+            "expired": vr.expired,
+            "notYetValid": vr.not_yet_valid,
+            "schemaStatus": vr.schema_status,
+            "consentStatus": vr.consent_status,
+            "pendingSignatures": vr.pending_signatures,
+            "signers": signers,
+        },
+    });
+    base_credential(
+        ctx,
+        attestation_id,
+        &vr.schema_id,
+        &vr.attester,
+        &vr.subject,
+        &vr.data_hash,
+        credential_status(ctx, attestation_id, None),
+        extension,
+    )
+}
+
+/// Describes a schema as the target of `credentialSchema.id`.
+pub fn schema_to_json(ctx: &VcContext, schema_id: &BytesN<32>, schema: &Schema) -> Value {
+    json!({
+        "@context": [CREDENTIALS_V2_CONTEXT, eas_context()],
+        "id": ctx.schema_uri(schema_id),
+        "type": "EasSorobanSchema",
+        "schemaUriHash": hex32(&schema.schema_uri_hash),
+        "creator": ctx.did(&schema.creator),
+        "revocable": schema.revocable,
+        "expiresAllowed": schema.expires_allowed,
+        "attesterMode": schema.attester_mode,
+        "renewable": schema.renewable,
+        "minValidity": schema.min_validity,
+        "maxValidity": schema.max_validity,
+        "expirationRequired": schema.expiration_required,
+        "status": schema.status,
+        "successorSchemaId": schema.successor_schema_id.as_ref().map(hex32),
+        "requiresSubjectConsent": schema.requires_subject_consent,
+        "subjectCanRenounce": schema.subject_can_renounce,
+        "saltedCommitments": schema.salted_commitments,
+        "merkleClaims": schema.merkle_claims,
+    })
+}
+
+fn field<'v>(v: &'v Value, name: &'static str) -> Result<&'v Value, VcError> {
+    v.get(name).ok_or(VcError::MissingField(name))
+}
+
+fn str_field<'v>(v: &'v Value, name: &'static str) -> Result<&'v str, VcError> {
+    field(v, name)?.as_str().ok_or(VcError::InvalidField(name))
+}
+
+fn bool_field(v: &Value, name: &'static str) -> Result<bool, VcError> {
+    field(v, name)?.as_bool().ok_or(VcError::InvalidField(name))
+}
+
+fn u64_field(v: &Value, name: &'static str) -> Result<u64, VcError> {
+    field(v, name)?.as_u64().ok_or(VcError::InvalidField(name))
+}
+
+fn u32_field(v: &Value, name: &'static str) -> Result<u32, VcError> {
+    u32::try_from(u64_field(v, name)?).map_err(|_| VcError::InvalidField(name))
+}
+
+fn opt_u64_field(v: &Value, name: &'static str) -> Result<Option<u64>, VcError> {
+    match v.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(x) => x.as_u64().map(Some).ok_or(VcError::InvalidField(name)),
+    }
+}
+
+fn parse_hex32(env: &Env, s: &str, name: &'static str) -> Result<BytesN<32>, VcError> {
+    let mut arr = [0u8; 32];
+    hex::decode_to_slice(s, &mut arr).map_err(|_| VcError::InvalidField(name))?;
+    Ok(BytesN::from_array(env, &arr))
+}
+
+// Every URI we emit is `<collection>/<hex>` under `ctx`'s contract.
+fn parse_uri_id(
+    env: &Env,
+    uri: &str,
+    collection: &str,
+    name: &'static str,
+) -> Result<BytesN<32>, VcError> {
+    if !uri.starts_with("stellar:") {
+        return Err(VcError::InvalidField(name));
+    }
+    let hex = uri
+        .strip_prefix(collection)
+        .and_then(|rest| rest.strip_prefix('/'))
+        .ok_or(VcError::OtherDeployment(name))?;
+    parse_hex32(env, hex, name)
+}
+
+fn parse_did(
+    env: &Env,
+    ctx: &VcContext,
+    did: &str,
+    name: &'static str,
+) -> Result<Address, VcError> {
+    let rest = did
+        .strip_prefix("did:pkh:stellar:")
+        .ok_or(VcError::InvalidField(name))?;
+    let key = rest
+        .strip_prefix(ctx.network)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .ok_or(VcError::OtherDeployment(name))?;
+    let is_strkey = key.len() == 56 && (key.starts_with('G') || key.starts_with('C'));
+    if !is_strkey || stellar_strkey::Strkey::from_string(key).is_err() {
+        return Err(VcError::InvalidField(name));
+    }
+    Ok(Address::from_string(&String::from_str(env, key)))
+}
+
+/// Imports a VC produced by [`attestation_to_vc`] for the deployment `ctx`,
+/// returning the attestation id and record. Ids and DIDs from another
+/// network or contract fail with [`VcError::OtherDeployment`].
+pub fn attestation_from_vc(
+    env: &Env,
+    ctx: &VcContext,
+    vc: &Value,
+) -> Result<(BytesN<32>, Attestation), VcError> {
+    let attestation_id = parse_uri_id(env, str_field(vc, "id")?, &ctx.attestations_uri(), "id")?;
+    let attester = parse_did(env, ctx, str_field(vc, "issuer")?, "issuer")?;
+
+    let cs = field(vc, "credentialSubject")?;
+    let subject = parse_did(env, ctx, str_field(cs, "id")?, "credentialSubject.id")?;
+    let data_hash = parse_hex32(env, str_field(cs, "dataHash")?, "dataHash")?;
+
+    let schema = field(vc, "credentialSchema")?;
+    let schema_id = parse_uri_id(
+        env,
+        str_field(schema, "id")?,
+        &ctx.schemas_uri(),
+        "credentialSchema.id",
+    )?;
+
+    let ext = field(vc, "easSoroban")?;
+    let status_index = match opt_u64_field(ext, "statusIndex")? {
+        Some(n) => Some(u32::try_from(n).map_err(|_| VcError::InvalidField("statusIndex"))?),
+        None => None,
+    };
+
+    Ok((
+        attestation_id,
+        Attestation {
+            schema_id,
+            attester,
+            subject,
+            data_hash,
+            timestamp: u64_field(ext, "timestamp")?,
+            valid_from: opt_u64_field(ext, "validFrom")?,
+            expiration: opt_u64_field(ext, "expiration")?,
+            revoked: bool_field(ext, "revoked")?,
+            pending_signatures: bool_field(ext, "pendingSignatures")?,
+            consent_status: u32_field(ext, "consentStatus")?,
+            renounced: bool_field(ext, "renounced")?,
+            status_index,
+            suspended: bool_field(ext, "suspended")?,
+        },
+    ))
+}
+
+/// Imports a schema description produced by [`schema_to_json`] for the
+/// deployment `ctx`.
+pub fn schema_from_json(
+    env: &Env,
+    ctx: &VcContext,
+    v: &Value,
+) -> Result<(BytesN<32>, Schema), VcError> {
+    let schema_id = parse_uri_id(env, str_field(v, "id")?, &ctx.schemas_uri(), "id")?;
+    let successor_schema_id = match field(v, "successorSchemaId")? {
+        Value::Null => None,
+        Value::String(s) => Some(parse_hex32(env, s, "successorSchemaId")?),
+        _ => return Err(VcError::InvalidField("successorSchemaId")),
+    };
+
+    Ok((
+        schema_id,
+        Schema {
+            schema_uri_hash: parse_hex32(env, str_field(v, "schemaUriHash")?, "schemaUriHash")?,
+            creator: parse_did(env, ctx, str_field(v, "creator")?, "creator")?,
+            revocable: bool_field(v, "revocable")?,
+            expires_allowed: bool_field(v, "expiresAllowed")?,
+            attester_mode: u32_field(v, "attesterMode")?,
+            renewable: bool_field(v, "renewable")?,
+            min_validity: opt_u64_field(v, "minValidity")?,
+            max_validity: opt_u64_field(v, "maxValidity")?,
+            expiration_required: bool_field(v, "expirationRequired")?,
+            status: u32_field(v, "status")?,
+            successor_schema_id,
+            requires_subject_consent: bool_field(v, "requiresSubjectConsent")?,
+            subject_can_renounce: bool_field(v, "subjectCanRenounce")?,
+            salted_commitments: bool_field(v, "saltedCommitments")?,
+            merkle_claims: bool_field(v, "merkleClaims")?,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{EasContract, EasContractClient, SchemaConfig};
+    use soroban_sdk::testutils::{Address as _, EnvTestConfig};
+
+    const CTX: VcContext<'static> = VcContext {
+        network: "testnet",
+        contract_id: "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAFCT4",
+    };
+
+    fn setup() -> (Env, EasContractClient<'static>) {
+        let mut env = Env::default();
+        env.set_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.mock_all_auths();
+        let contract_id = env.register(EasContract, ());
+        let client = EasContractClient::new(&env, &contract_id);
+        (env, client)
+    }
+
+    #[test]
+    fn attestation_round_trip() {
+        let (env, client) = setup();
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let schema_hash = BytesN::from_array(&env, &[1u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &true, &0u32);
+        let data_hash = BytesN::from_array(&env, &[2u8; 32]);
+        let att_id = client.attest(
+            &attester,
+            &schema_id,
+            &subject,
+            &data_hash,
+            &None,
+            &Some(1000u64),
+            &1u64,
+        );
+        let att = client.get_attestation(&att_id);
+
+        let vc = attestation_to_vc(&CTX, &att_id, &att);
+        assert_eq!(vc["type"][0], "VerifiableCredential");
+        assert_eq!(vc["issuer"], CTX.did(&attester));
+        assert!(vc["issuer"]
+            .as_str()
+            .unwrap()
+            .starts_with("did:pkh:stellar:testnet:C"));
+        assert_eq!(vc["credentialStatus"][0]["contract"], CTX.contract_id);
+        assert_eq!(
+            vc["credentialStatus"][1]["type"],
+            "EasSorobanStatusListEntry"
+        );
+
+        // Every type and property outside the VC v2 context is defined inline.
+        let context = &vc["@context"][1];
+        for term in [
+            "EasSorobanAttestation",
+            "EasSorobanSchema",
+            "EasSorobanVerify",
+            "EasSorobanStatusListEntry",
+            "dataHash",
+            "easSoroban",
+        ] {
+            assert!(!context[term].is_null(), "{term} is not defined");
+        }
+        assert_eq!(context["easSoroban"]["@type"], "@json");
+
+        // Survives serialization, too.
+        let text = serde_json::to_string(&vc).unwrap();
+        let parsed: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(attestation_from_vc(&env, &CTX, &parsed), Ok((att_id, att)));
+    }
+
+    #[test]
+    fn schema_round_trip() {
+        let (env, client) = setup();
+        let creator = Address::generate(&env);
+
+        let successor_hash = BytesN::from_array(&env, &[3u8; 32]);
+        let successor = client.create_schema_with_config(
+            &creator,
+            &successor_hash,
+            &true,
+            &true,
+            &0u32,
+            &SchemaConfig {
+                renewable: true,
+                ..Default::default()
+            },
+            &None,
+        );
+        let schema_hash = BytesN::from_array(&env, &[4u8; 32]);
+        let schema_id = client.create_schema_with_config(
+            &creator,
+            &schema_hash,
+            &false,
+            &true,
+            &1u32,
+            &SchemaConfig {
+                renewable: true,
+                ..Default::default()
+            },
+            &None,
+        );
+        client.set_validity_policy(&creator, &schema_id, &Some(5u64), &None, &true);
+        client.set_schema_status(&creator, &schema_id, &1u32, &Some(successor));
+        let schema = client.get_schema(&schema_id);
+
+        let v = schema_to_json(&CTX, &schema_id, &schema);
+        assert_eq!(schema_from_json(&env, &CTX, &v), Ok((schema_id, schema)));
+    }
+
+    #[test]
+    fn verify_result_export() {
+        let (env, client) = setup();
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let schema_hash = BytesN::from_array(&env, &[5u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &false, &0u32);
+        let data_hash = BytesN::from_array(&env, &[6u8; 32]);
+        let att_id = client.attest(
+            &attester, &schema_id, &subject, &data_hash, &None, &None, &1u64,
+        );
+        client.revoke_by(&attester, &att_id);
+
+        let vc = verify_result_to_vc(&CTX, &att_id, &client.verify(&att_id).unwrap());
+        assert_eq!(vc["easSoroban"]["status"]["valid"], false);
+        assert_eq!(vc["easSoroban"]["status"]["revoked"], true);
+        assert_eq!(vc["credentialSubject"]["id"], CTX.did(&subject));
+    }
+
+    #[test]
+    fn rejects_foreign_issuer() {
+        let (env, client) = setup();
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let schema_hash = BytesN::from_array(&env, &[7u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &false, &0u32);
+        let data_hash = BytesN::from_array(&env, &[8u8; 32]);
+        let att_id = client.attest(
+            &attester, &schema_id, &subject, &data_hash, &None, &None, &1u64,
+        );
+
+        let mut vc = attestation_to_vc(&CTX, &att_id, &client.get_attestation(&att_id));
+        vc["issuer"] = json!("did:web:example.com");
+        assert_eq!(
+            attestation_from_vc(&env, &CTX, &vc),
+            Err(VcError::InvalidField("issuer"))
+        );
+    }
+
+    #[test]
+    fn rejects_other_deployments() {
+        let (env, client) = setup();
+        let creator = Address::generate(&env);
+        let attester = Address::generate(&env);
+        let subject = Address::generate(&env);
+
+        let schema_hash = BytesN::from_array(&env, &[9u8; 32]);
+        let schema_id = client.create_schema(&creator, &schema_hash, &true, &false, &0u32);
+        let data_hash = BytesN::from_array(&env, &[10u8; 32]);
+        let att_id = client.attest(
+            &attester, &schema_id, &subject, &data_hash, &None, &None, &1u64,
+        );
+        let vc = attestation_to_vc(&CTX, &att_id, &client.get_attestation(&att_id));
+
+        let other_contract = VcContext {
+            network: CTX.network,
+            contract_id: "CBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB",
+        };
+        assert_eq!(
+            attestation_from_vc(&env, &other_contract, &vc),
+            Err(VcError::OtherDeployment("id"))
+        );
+        let other_network = VcContext {
+            network: "pubnet",
+            contract_id: CTX.contract_id,
+        };
+        assert_eq!(
+            attestation_from_vc(&env, &other_network, &vc),
+            Err(VcError::OtherDeployment("id"))
+        );
+
+        // Same contract, but an issuer DID from another network.
+        let mut forged = vc.clone();
+        forged["issuer"] = json!(forged["issuer"]
+            .as_str()
+            .unwrap()
+            .replace(":testnet:", ":pubnet:"));
+        assert_eq!(
+            attestation_from_vc(&env, &CTX, &forged),
+            Err(VcError::OtherDeployment("issuer"))
+        );
+
+        let schema = schema_to_json(&CTX, &schema_id, &client.get_schema(&schema_id));
+        assert_eq!(
+            schema_from_json(&env, &other_contract, &schema),
+            Err(VcError::OtherDeployment("id"))
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! Delegated attestations: the signer (attester, revoker or schema creator)
+//! pre-signs a Soroban authorization entry for one exact call, and anyone can
+//! later submit it in a transaction they pay for.
+//!
+//! Only the root invocation is covered. Calls on schemas with an attestation
+//! fee also need the token transfer authorized, which has to be done online.
+
+use ed25519_dalek::{Signer, SigningKey};
+use sha2::{Digest, Sha256};
+use soroban_sdk::xdr::{
+    Hash, HashIdPreimage, HashIdPreimageSorobanAuthorization, Limits, ScAddress, ScBytes, ScMap,
+    ScMapEntry, ScSymbol, ScVal, SorobanAddressCredentials, SorobanAuthorizationEntry,
+    SorobanAuthorizedFunction, SorobanAuthorizedInvocation, SorobanCredentials, StringM, WriteXdr,
+};
+
+use crate::call::Call;
+use crate::{Error, Result};
+
+pub fn network_id(passphrase: &str) -> [u8; 32] {
+    Sha256::digest(passphrase.as_bytes()).into()
+}
+
+pub fn invocation(contract: &ScAddress, call: &Call) -> SorobanAuthorizedInvocation {
+    SorobanAuthorizedInvocation {
+        function: SorobanAuthorizedFunction::ContractFn(call.invoke_args(contract)),
+        sub_invocations: Default::default(),
+    }
+}
+
+/// Builds and signs the authorization entry for `call`. `nonce` must be
+/// unique per signer; the entry is rejected after `expiration_ledger`.
+pub fn sign_auth(
+    secret: &str,
+    network_passphrase: &str,
+    contract: &ScAddress,
+    call: &Call,
+    nonce: i64,
+    expiration_ledger: u32,
+) -> Result<SorobanAuthorizationEntry> {
+    let seed = stellar_strkey::ed25519::PrivateKey::from_string(secret)
+        .map_err(|_| Error::InvalidArg("secret"))?;
+    let key = SigningKey::from_bytes(&seed.0);
+    let public = key.verifying_key().to_bytes();
+
+    let signer = crate::parse_address(
+        stellar_strkey::ed25519::PublicKey(public)
+            .to_string()
+            .as_str(),
+    )?;
+    if &signer != call.signer() {
+        return Err(Error::SignerMismatch);
+    }
+
+    let invocation = invocation(contract, call);
+    let preimage = HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
+        network_id: Hash(network_id(network_passphrase)),
+        nonce,
+        signature_expiration_ledger: expiration_ledger,
+        invocation: invocation.clone(),
+    });
+    let payload: [u8; 32] = Sha256::digest(preimage.to_xdr(Limits::none())?).into();
+    let signature = key.sign(&payload).to_bytes();
+
+    // Account signatures are a Vec of {public_key, signature} maps.
+    let entry = ScVal::Map(Some(ScMap(
+        vec![
+            ScMapEntry {
+                key: symbol("public_key"),
+                val: ScVal::Bytes(ScBytes(public.to_vec().try_into().unwrap())),
+            },
+            ScMapEntry {
+                key: symbol("signature"),
+                val: ScVal::Bytes(ScBytes(signature.to_vec().try_into().unwrap())),
+            },
+        ]
+        .try_into()
+        .unwrap(),
+    )));
+
+    Ok(SorobanAuthorizationEntry {
+        credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+            address: signer,
+            nonce,
+            signature_expiration_ledger: expiration_ledger,
+            signature: ScVal::Vec(Some(vec![entry].try_into().unwrap())),
+        }),
+        root_invocation: invocation,
+    })
+}
+
+fn symbol(s: &str) -> ScVal {
+    ScVal::Symbol(ScSymbol(StringM::try_from(s).unwrap()))
+}
@@ -0,0 +1,128 @@
+//! Merkle trees for `attest_merkle_root`, hashed the way `verify_in_batch`
+//! checks them: leaf = `sha256(0x00 || index_be || subject_xdr || data_hash)`,
+//! node = `sha256(0x01 || left || right)`, positional by leaf index. Levels
+//! with an odd number of nodes are padded with 32 zero bytes; the contract
+//! never accepts an index at or past the batch count, so padding cannot be
+//! presented as a leaf.
+
+use sha2::{Digest, Sha256};
+use soroban_sdk::xdr::{Limits, ScAddress, ScVal, WriteXdr};
+
+use crate::{Error, Result};
+
+const LEAF_TAG: u8 = 0x00;
+const NODE_TAG: u8 = 0x01;
+
+/// Same bound as the contract's `MAX_BATCH_SIZE`.
+pub const MAX_BATCH_SIZE: usize = 1 << 20;
+
+#[derive(Clone, Debug)]
+pub struct Leaf {
+    pub subject: ScAddress,
+    pub data_hash: [u8; 32],
+}
+
+pub fn leaf_hash(index: u32, leaf: &Leaf) -> Result<[u8; 32]> {
+    let subject = ScVal::Address(leaf.subject.clone()).to_xdr(Limits::none())?;
+    let mut h = Sha256::new();
+    h.update([LEAF_TAG]);
+    h.update(index.to_be_bytes());
+    h.update(subject);
+    h.update(leaf.data_hash);
+    Ok(h.finalize().into())
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut h = Sha256::new();
+    h.update([NODE_TAG]);
+    h.update(left);
+    h.update(right);
+    h.finalize().into()
+}
+
+/// All levels of the tree, leaf hashes first and the root last.
+#[derive(Clone, Debug)]
+pub struct Tree {
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl Tree {
+    pub fn build(leaves: &[Leaf]) -> Result<Self> {
+        if leaves.is_empty() || leaves.len() > MAX_BATCH_SIZE {
+            return Err(Error::InvalidArg("leaves"));
+        }
+        let mut level = leaves
+            .iter()
+            .enumerate()
+            .map(|(i, leaf)| leaf_hash(i as u32, leaf))
+            .collect::<Result<Vec<_>>>()?;
+        let mut levels = Vec::new();
+        while level.len() > 1 {
+            if level.len() % 2 == 1 {
+                level.push([0u8; 32]);
+            }
+            let next = level.chunks(2).map(|p| node_hash(&p[0], &p[1])).collect();
+            levels.push(level);
+            level = next;
+        }
+        levels.push(level);
+        Ok(Tree { levels })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels.last().unwrap()[0]
+    }
+
+    pub fn leaf(&self, index: usize) -> [u8; 32] {
+        self.levels[0][index]
+    }
+
+    /// Siblings from the leaf up, as `verify_in_batch` expects them.
+    pub fn proof(&self, index: usize) -> Vec<[u8; 32]> {
+        let mut idx = index;
+        let mut proof = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            proof.push(level[idx ^ 1]);
+            idx >>= 1;
+        }
+        proof
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn leaf(b: u8) -> Leaf {
+        Leaf {
+            subject: ScAddress::Contract(soroban_sdk::xdr::ContractId(soroban_sdk::xdr::Hash(
+                [b; 32],
+            ))),
+            data_hash: [b; 32],
+        }
+    }
+
+    #[test]
+    fn proofs_rebuild_the_root() {
+        let leaves: Vec<Leaf> = (1..=5).map(leaf).collect();
+        let tree = Tree::build(&leaves).unwrap();
+        for i in 0..leaves.len() {
+            let mut node = tree.leaf(i);
+            let mut idx = i;
+            for sibling in tree.proof(i) {
+                node = if idx & 1 == 0 {
+                    node_hash(&node, &sibling)
+                } else {
+                    node_hash(&sibling, &node)
+                };
+                idx >>= 1;
+            }
+            assert_eq!(node, tree.root());
+        }
+
+        let single = Tree::build(&leaves[..1]).unwrap();
+        assert_eq!(single.root(), leaf_hash(0, &leaves[0]).unwrap());
+        assert!(single.proof(0).is_empty());
+        assert!(Tree::build(&[]).is_err());
+    }
+}
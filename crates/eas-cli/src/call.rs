@@ -0,0 +1,132 @@
+//! Contract calls the CLI can encode, with their arguments in contract order.
+
+use clap::Subcommand;
+use soroban_sdk::xdr::{InvokeContractArgs, ScAddress, ScBytes, ScSymbol, ScVal, StringM};
+
+use crate::{ids, parse_address, parse_hex32, Result};
+
+fn address_arg(s: &str) -> Result<ScAddress> {
+    parse_address(s)
+}
+
+fn hex32_arg(s: &str) -> Result<[u8; 32]> {
+    parse_hex32(s)
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum Call {
+    /// attest(attester, schema_id, subject, data_hash, valid_from, expiration, nonce)
+    Attest {
+        #[arg(long, value_parser = address_arg)]
+        attester: ScAddress,
+        #[arg(long, value_parser = hex32_arg)]
+        schema_id: [u8; 32],
+        #[arg(long, value_parser = address_arg)]
+        subject: ScAddress,
+        #[arg(long, value_parser = hex32_arg)]
+        data_hash: [u8; 32],
+        #[arg(long)]
+        valid_from: Option<u64>,
+        #[arg(long)]
+        expiration: Option<u64>,
+        /// Attester nonce: `get_nonce(attester) + 1`.
+        #[arg(long)]
+        nonce: u64,
+    },
+    /// revoke_by(revoker, attestation_id)
+    RevokeBy {
+        #[arg(long, value_parser = address_arg)]
+        revoker: ScAddress,
+        #[arg(long, value_parser = hex32_arg)]
+        attestation_id: [u8; 32],
+    },
+    /// create_schema(creator, sha256(schema_uri), revocable, expires_allowed,
+    /// attester_mode)
+    CreateSchema {
+        #[arg(long, value_parser = address_arg)]
+        creator: ScAddress,
+        #[arg(long)]
+        schema_uri: String,
+        #[arg(long)]
+        revocable: bool,
+        #[arg(long)]
+        expires_allowed: bool,
+        /// 0 = permissionless, 1 = issuer only.
+        #[arg(long, default_value_t = 0)]
+        attester_mode: u32,
+    },
+}
+
+impl Call {
+    pub fn function(&self) -> &'static str {
+        match self {
+            Call::Attest { .. } => "attest",
+            Call::RevokeBy { .. } => "revoke_by",
+            Call::CreateSchema { .. } => "create_schema",
+        }
+    }
+
+    /// The address whose `require_auth` the call triggers.
+    pub fn signer(&self) -> &ScAddress {
+        match self {
+            Call::Attest { attester, .. } => attester,
+            Call::RevokeBy { revoker, .. } => revoker,
+            Call::CreateSchema { creator, .. } => creator,
+        }
+    }
+
+    pub fn args(&self) -> Vec<ScVal> {
+        match self {
+            Call::Attest {
+                attester,
+                schema_id,
+                subject,
+                data_hash,
+                valid_from,
+                expiration,
+                nonce,
+            } => vec![
+                ScVal::Address(attester.clone()),
+                bytes(schema_id),
+                ScVal::Address(subject.clone()),
+                bytes(data_hash),
+                opt_u64(*valid_from),
+                opt_u64(*expiration),
+                ScVal::U64(*nonce),
+            ],
+            Call::RevokeBy {
+                revoker,
+                attestation_id,
+            } => vec![ScVal::Address(revoker.clone()), bytes(attestation_id)],
+            Call::CreateSchema {
+                creator,
+                schema_uri,
+                revocable,
+                expires_allowed,
+                attester_mode,
+            } => vec![
+                ScVal::Address(creator.clone()),
+                bytes(&ids::schema_id(schema_uri)),
+                ScVal::Bool(*revocable),
+                ScVal::Bool(*expires_allowed),
+                ScVal::U32(*attester_mode),
+            ],
+        }
+    }
+
+    pub fn invoke_args(&self, contract: &ScAddress) -> InvokeContractArgs {
+        InvokeContractArgs {
+            contract_address: contract.clone(),
+            function_name: ScSymbol(StringM::try_from(self.function()).unwrap()),
+            args: self.args().try_into().unwrap(),
+        }
+    }
+}
+
+fn bytes(b: &[u8; 32]) -> ScVal {
+    ScVal::Bytes(ScBytes(b.to_vec().try_into().unwrap()))
+}
+
+fn opt_u64(v: Option<u64>) -> ScVal {
+    v.map_or(ScVal::Void, ScVal::U64)
+}
@@ -0,0 +1,280 @@
+//! XDR to JSON: generic `ScVal` conversion for return values, and typed
+//! output for the events `eas_soroban::client` understands.
+
+use eas_soroban::client::{self, EasEvent, EventFormat};
+use serde_json::{json, Map, Value};
+use soroban_sdk::xdr::{Limits, ReadXdr, ScVal};
+
+use crate::{Error, Result};
+
+pub fn scval_from_base64(b64: &str) -> Result<ScVal> {
+    Ok(ScVal::from_xdr_base64(b64, Limits::none())?)
+}
+
+/// Bytes become hex, addresses strkeys, 128/256-bit integers decimal
+/// strings, and maps with symbol/string keys JSON objects (other maps are
+/// lists of `[key, value]` pairs).
+pub fn scval_to_json(v: &ScVal) -> Value {
+    match v {
+        ScVal::Void => Value::Null,
+        ScVal::Bool(b) => json!(b),
+        ScVal::U32(n) => json!(n),
+        ScVal::I32(n) => json!(n),
+        ScVal::U64(n) => json!(n),
+        ScVal::I64(n) => json!(n),
+        ScVal::Timepoint(t) => json!(t.0),
+        ScVal::Duration(d) => json!(d.0),
+        ScVal::U128(p) => json!((u128::from(p.hi) << 64 | u128::from(p.lo)).to_string()),
+        ScVal::I128(p) => json!((i128::from(p.hi) << 64 | i128::from(p.lo)).to_string()),
+        ScVal::Bytes(b) => json!(hex::encode(b.as_slice())),
+        ScVal::String(s) => json!(s.to_utf8_string_lossy()),
+        ScVal::Symbol(s) => json!(s.to_utf8_string_lossy()),
+        ScVal::Address(a) => json!(a.to_string()),
+        ScVal::Vec(Some(items)) => Value::Array(items.iter().map(scval_to_json).collect()),
+        ScVal::Map(Some(map)) => {
+            let keyed: Option<Map<String, Value>> = map
+                .iter()
+                .map(|e| match &e.key {
+                    ScVal::Symbol(s) => Some((s.to_utf8_string_lossy(), scval_to_json(&e.val))),
+                    ScVal::String(s) => Some((s.to_utf8_string_lossy(), scval_to_json(&e.val))),
+                    _ => None,
+                })
+                .collect();
+            match keyed {
+                Some(obj) => Value::Object(obj),
+                None => Value::Array(
+                    map.iter()
+                        .map(|e| json!([scval_to_json(&e.key), scval_to_json(&e.val)]))
+                        .collect(),
+                ),
+            }
+        }
+        // U256/I256, errors, ledger keys, contract instances: rare in EAS
+        // output, shown as their XDR debug form.
+        other => json!(format!("{other:?}")),
+    }
+}
+
+/// Typed JSON for the events `eas_soroban::client` decodes (either format),
+/// generic `{topics, data}` for every other event.
+pub fn event_to_json(topics: &[String], value: &str) -> Result<Value> {
+    let topics = topics
+        .iter()
+        .map(|t| scval_from_base64(t))
+        .collect::<Result<Vec<_>>>()?;
+    let value = scval_from_base64(value)?;
+
+    let decoded = client::decode(&topics, &value).map_err(|_| Error::InvalidArg("event"))?;
+    let Some(decoded) = decoded else {
+        return Ok(json!({
+            "topics": topics.iter().map(scval_to_json).collect::<Vec<_>>(),
+            "data": scval_to_json(&value),
+        }));
+    };
+
+    let (name, fields) = match &decoded.event {
+        EasEvent::SchemaCreated(e) => (
+            "SchemaCreated",
+            json!({
+                "schema_id": hex::encode(e.schema_id),
+                "creator": e.creator,
+                "schema_uri_hash": hex::encode(e.schema_uri_hash),
+                "revocable": e.revocable,
+                "expires_allowed": e.expires_allowed,
+                "attester_mode": e.attester_mode,
+                "ledger": e.ledger,
+            }),
+        ),
+        EasEvent::Attested(e) => (
+            "Attested",
+            json!({
+                "attestation_id": hex::encode(e.attestation_id),
+                "schema_id": hex::encode(e.schema_id),
+                "attester": e.attester,
+                "subject": e.subject,
+                "data_hash": hex::encode(e.data_hash),
+                "timestamp": e.timestamp,
+                "expiration": e.expiration,
+                "status_index": e.status_index,
+            }),
+        ),
+        EasEvent::Revoked(e) => (
+            "Revoked",
+            json!({
+                "attestation_id": hex::encode(e.attestation_id),
+                "revoker": e.revoker,
+                "ledger": e.ledger,
+                "schema_id": e.schema_id.map(hex::encode),
+                "attester": e.attester,
+                "subject": e.subject,
+            }),
+        ),
+        EasEvent::Renewed(e) => (
+            "Renewed",
+            json!({
+                "attestation_id": hex::encode(e.attestation_id),
+                "attester": e.attester,
+                "previous_expiration": e.previous_expiration,
+                "new_expiration": e.new_expiration,
+                "ledger": e.ledger,
+            }),
+        ),
+        EasEvent::Suspended(e) => ("Suspended", status_json(e, "attester")),
+        EasEvent::Reinstated(e) => ("Reinstated", status_json(e, "attester")),
+        EasEvent::Renounced(e) => ("Renounced", status_json(e, "subject")),
+        EasEvent::Accepted(e) => ("AttestationAccepted", status_json(e, "subject")),
+        EasEvent::Rejected(e) => ("AttestationRejected", status_json(e, "subject")),
+        EasEvent::StatusListRevoked(e) => (
+            "StatusListRevoked",
+            json!({
+                "attester": e.attester,
+                "indices": e.indices,
+                "ledger": e.ledger,
+            }),
+        ),
+        EasEvent::SchemaFlagUpdated(e) => (
+            "SchemaFlagUpdated",
+            json!({
+                "schema_id": hex::encode(e.schema_id),
+                "flag": e.flag,
+                "value": e.value,
+                "ledger": e.ledger,
+            }),
+        ),
+        EasEvent::SchemaStatusChanged(e) => (
+            "SchemaStatusChanged",
+            json!({
+                "schema_id": hex::encode(e.schema_id),
+                "status": e.status,
+                "successor_schema_id": e.successor_schema_id.map(hex::encode),
+                "ledger": e.ledger,
+            }),
+        ),
+        EasEvent::SchemaMetadataUpdated(e) => (
+            "SchemaMetadataUpdated",
+            json!({
+                "schema_id": hex::encode(e.schema_id),
+                "metadata": e.metadata.as_ref().map(|m| json!({
+                    "name": m.name,
+                    "description": m.description,
+                    "version": { "major": m.version.0, "minor": m.version.1, "patch": m.version.2 },
+                    "tags": m.tags,
+                })),
+                "ledger": e.ledger,
+            }),
+        ),
+        EasEvent::BatchAttested(e) => (
+            "BatchAttested",
+            json!({
+                "root_id": hex::encode(e.root_id),
+                "schema_id": hex::encode(e.schema_id),
+                "attester": e.attester,
+                "root": hex::encode(e.root),
+                "count": e.count,
+                "timestamp": e.timestamp,
+                "expiration": e.expiration,
+            }),
+        ),
+        EasEvent::BatchRevoked(e) => (
+            match e.indices {
+                Some(_) => "BatchLeavesRevoked",
+                None => "BatchRevoked",
+            },
+            json!({
+                "root_id": hex::encode(e.root_id),
+                "revoker": e.revoker,
+                "indices": e.indices,
+                "ledger": e.ledger,
+            }),
+        ),
+    };
+    Ok(json!({
+        "event": name,
+        "format": match decoded.format {
+            EventFormat::Legacy => "legacy",
+            EventFormat::V2 => "v2",
+        },
+        "seq": decoded.seq,
+        "fields": fields,
+    }))
+}
+
+fn status_json(e: &client::AttestationStatusEvent, actor: &str) -> Value {
+    json!({
+        "attestation_id": hex::encode(e.attestation_id),
+        actor: e.actor,
+        "ledger": e.ledger,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const GOLDEN: &str = include_str!("../../../contracts/eas/testdata/events.json");
+    const STATUS_GOLDEN: &str = include_str!("../../../contracts/eas/testdata/status_events.json");
+
+    fn golden_event(i: usize) -> (Vec<String>, String) {
+        nth_event(GOLDEN, i)
+    }
+
+    fn nth_event(text: &str, i: usize) -> (Vec<String>, String) {
+        let golden: Value = serde_json::from_str(text).unwrap();
+        let e = &golden["events"][i];
+        let topics = e["topic"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t.as_str().unwrap().to_owned())
+            .collect();
+        (topics, e["value"].as_str().unwrap().to_owned())
+    }
+
+    #[test]
+    fn decodes_golden_events() {
+        let (topics, value) = golden_event(3);
+        let out = event_to_json(&topics, &value).unwrap();
+        assert_eq!(out["event"], "Attested");
+        assert_eq!(out["format"], "v2");
+        assert_eq!(out["seq"], 2);
+        assert_eq!(out["fields"]["data_hash"], hex::encode([4u8; 32]));
+        assert_eq!(out["fields"]["expiration"], 100);
+
+        // Raw view of the same value: a map keyed by field name.
+        let raw = scval_to_json(&scval_from_base64(&value).unwrap());
+        assert_eq!(raw["seq"], 2);
+        assert_eq!(raw["attestation_id"], out["fields"]["attestation_id"]);
+
+        let (topics, value) = golden_event(4);
+        let out = event_to_json(&topics, &value).unwrap();
+        assert_eq!(out["event"], "Revoked");
+        assert_eq!(out["format"], "legacy");
+        // Same seq as the v2 copy that follows it.
+        assert_eq!(out["seq"], 3);
+        assert_eq!(out["fields"]["schema_id"], Value::Null);
+    }
+    #[test]
+    fn decodes_status_events() {
+        let golden: Value = serde_json::from_str(STATUS_GOLDEN).unwrap();
+        let events: Vec<Value> = (0..golden["events"].as_array().unwrap().len())
+            .map(|i| {
+                let (topics, value) = nth_event(STATUS_GOLDEN, i);
+                event_to_json(&topics, &value).unwrap()
+            })
+            .collect();
+        let find = |name: &str| events.iter().find(|e| e["event"] == name).unwrap();
+
+        let suspended = find("Suspended");
+        assert_eq!(suspended["format"], "legacy");
+        assert!(suspended["fields"]["attester"].is_string());
+        assert!(find("Renounced")["fields"]["subject"].is_string());
+        assert_eq!(find("StatusListRevoked")["fields"]["indices"], json!([1]));
+        assert_eq!(find("BatchLeavesRevoked")["fields"]["indices"], json!([2]));
+        assert_eq!(find("BatchRevoked")["fields"]["indices"], Value::Null);
+        assert_eq!(find("SchemaStatusChanged")["fields"]["status"], 1);
+        assert!(find("SchemaStatusChanged")["fields"]["successor_schema_id"].is_string());
+        let metadata = &find("SchemaMetadataUpdated")["fields"]["metadata"];
+        assert_eq!(metadata["version"]["minor"], 2);
+        assert_eq!(metadata["tags"], json!(["identity"]));
+    }
+}
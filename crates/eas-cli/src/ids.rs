@@ -0,0 +1,57 @@
+//! Identifiers the contract derives deterministically.
+
+use sha2::{Digest, Sha256};
+
+/// `schema_id == schema_uri_hash == sha256(schema_uri)`, the same hash
+/// `apps/api` passes to `create_schema`.
+pub fn schema_id(schema_uri: &str) -> [u8; 32] {
+    Sha256::digest(schema_uri.as_bytes()).into()
+}
+
+/// Id of the `counter`-th attestation (1-based): the counter big-endian in
+/// the last 8 bytes, zeros elsewhere.
+pub fn attestation_id(counter: u64) -> [u8; 32] {
+    let mut id = [0u8; 32];
+    id[24..].copy_from_slice(&counter.to_be_bytes());
+    id
+}
+
+/// Id of the `counter`-th Merkle batch (1-based):
+/// `sha256("eas-soroban:batch" || attestation_id(counter))`, so it never
+/// collides with an attestation id.
+pub fn batch_id(counter: u64) -> [u8; 32] {
+    let mut h = Sha256::new();
+    h.update(b"eas-soroban:batch");
+    h.update(attestation_id(counter));
+    h.finalize().into()
+}
+
+/// Inverse of [`attestation_id`]; `None` if `id` does not have that layout.
+pub fn attestation_counter(id: &[u8; 32]) -> Option<u64> {
+    if id[..24].iter().any(|b| *b != 0) {
+        return None;
+    }
+    Some(u64::from_be_bytes(id[24..].try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ids_round_trip() {
+        let id = attestation_id(258);
+        assert_eq!(
+            hex::encode(id),
+            "0000000000000000000000000000000000000000000000000000000000000102"
+        );
+        assert_eq!(attestation_counter(&id), Some(258));
+        assert_eq!(attestation_counter(&[1u8; 32]), None);
+        assert_eq!(attestation_counter(&batch_id(258)), None);
+
+        assert_eq!(
+            hex::encode(schema_id("ipfs://local/schema.json")),
+            hex::encode(Sha256::digest(b"ipfs://local/schema.json"))
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Offline building blocks for `eas-cli`: ids, Merkle batch trees, contract
+//! invocations, delegated (pre-signed) authorization entries, unsigned
+//! transactions and XDR-to-JSON decoding. Claim encoding lives in `eas_soroban::encoder`. Nothing here talks to the network.
+
+pub mod auth;
+pub mod batch;
+pub mod call;
+pub mod decode;
+pub mod ids;
+pub mod tx;
+
+use soroban_sdk::xdr::ScAddress;
+
+#[derive(Debug)]
+pub enum Error {
+    InvalidArg(&'static str),
+    /// The secret key does not belong to the address the call authorizes.
+    SignerMismatch,
+    Xdr(soroban_sdk::xdr::Error),
+    Encode(eas_soroban::encoder::host::EncodeError),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidArg(name) => write!(f, "invalid `{name}`"),
+            Error::SignerMismatch => write!(f, "secret key does not match the call's signer"),
+            Error::Xdr(e) => write!(f, "xdr: {e}"),
+            Error::Encode(e) => write!(f, "encode: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<soroban_sdk::xdr::Error> for Error {
+    fn from(e: soroban_sdk::xdr::Error) -> Self {
+        Error::Xdr(e)
+    }
+}
+
+impl From<eas_soroban::encoder::host::EncodeError> for Error {
+    fn from(e: eas_soroban::encoder::host::EncodeError) -> Self {
+        Error::Encode(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// `G...` account or `C...` contract strkey.
+pub fn parse_address(s: &str) -> Result<ScAddress> {
+    s.parse().map_err(|_| Error::InvalidArg("address"))
+}
+
+/// 64 hex chars.
+pub fn parse_hex32(s: &str) -> Result<[u8; 32]> {
+    let mut out = [0u8; 32];
+    hex::decode_to_slice(s.trim_start_matches("0x"), &mut out)
+        .map_err(|_| Error::InvalidArg("hex32"))?;
+    Ok(out)
+}
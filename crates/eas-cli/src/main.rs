@@ -0,0 +1,198 @@
+use clap::{Parser, Subcommand};
+use eas_cli::call::Call;
+use eas_cli::{auth, batch, decode, ids, parse_address, parse_hex32, tx};
+use eas_soroban::encoder::host as encoder;
+use soroban_sdk::xdr::{Limits, ReadXdr, SorobanAuthorizationEntry, WriteXdr};
+
+#[derive(Parser)]
+#[command(
+    name = "eas-cli",
+    about = "Offline helpers for the EAS contract: ids, delegated auth, tx XDR, decoding"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// sha256 of the schema URI, as passed to create_schema.
+    SchemaId { uri: String },
+    /// Canonical claim encoding and data_hash for a schema definition.
+    EncodeData {
+        /// e.g. "u64 score, string name, address issuer".
+        #[arg(long)]
+        schema: String,
+        /// JSON object keyed by field name.
+        #[arg(long)]
+        values: String,
+    },
+    /// Id of the N-th attestation.
+    AttestationId { counter: u64 },
+    /// Counter encoded in an attestation id.
+    AttestationCounter { id: String },
+    /// Id of the N-th Merkle batch (attest_merkle_root).
+    BatchId { counter: u64 },
+    /// Root for attest_merkle_root and per-leaf proofs for verify_in_batch.
+    BatchTree {
+        /// JSON array of {"subject": "G...", "data_hash": "<hex32>"}, in
+        /// leaf index order.
+        #[arg(long)]
+        leaves: String,
+    },
+    /// Pre-sign the authorization entry for one call (base64 XDR).
+    SignAuth {
+        /// Signer secret key (S...).
+        #[arg(long, env = "EAS_SIGNER_SECRET", hide_env_values = true)]
+        secret: String,
+        #[arg(
+            long,
+            env = "SOROBAN_NETWORK_PASSPHRASE",
+            default_value = "Test SDF Network ; September 2015"
+        )]
+        network_passphrase: String,
+        #[arg(long, env = "SOROBAN_CONTRACT_ID")]
+        contract: String,
+        /// Unique per signer; pick a random value.
+        #[arg(long)]
+        nonce: i64,
+        /// Last ledger at which the signature is accepted.
+        #[arg(long)]
+        expiration_ledger: u32,
+        #[command(subcommand)]
+        call: Call,
+    },
+    /// Unsigned transaction envelope for one call (base64 XDR). Simulate it
+    /// before signing.
+    BuildTx {
+        /// Fee-paying source account (G...).
+        #[arg(long)]
+        source: String,
+        /// Sequence number for this transaction (account sequence + 1).
+        #[arg(long)]
+        seq: i64,
+        #[arg(long, default_value_t = 100)]
+        fee: u32,
+        #[arg(long, env = "SOROBAN_CONTRACT_ID")]
+        contract: String,
+        /// Authorization entries from `sign-auth`.
+        #[arg(long)]
+        auth: Vec<String>,
+        #[command(subcommand)]
+        call: Call,
+    },
+    /// Event topics and value (base64 XDR) to JSON.
+    DecodeEvent {
+        #[arg(long, required = true)]
+        topic: Vec<String>,
+        #[arg(long)]
+        value: String,
+    },
+    /// A single ScVal (e.g. a return value, base64 XDR) to JSON.
+    DecodeScval { value: String },
+}
+
+fn main() {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(out) => println!("{out}"),
+        Err(e) => {
+            eprintln!("eas-cli: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run(cli: Cli) -> eas_cli::Result<String> {
+    Ok(match cli.command {
+        Command::SchemaId { uri } => hex::encode(ids::schema_id(&uri)),
+        Command::EncodeData { schema, values } => {
+            let values: serde_json::Map<String, serde_json::Value> =
+                serde_json::from_str(&values).map_err(|_| eas_cli::Error::InvalidArg("values"))?;
+            let encoded = encoder::encode_json(&schema, &values)?;
+            serde_json::to_string_pretty(&serde_json::json!({
+                "encoded": hex::encode(&encoded),
+                "data_hash": hex::encode(encoder::data_hash(&encoded)),
+            }))
+            .unwrap()
+        }
+        Command::AttestationId { counter } => hex::encode(ids::attestation_id(counter)),
+        Command::AttestationCounter { id } => ids::attestation_counter(&parse_hex32(&id)?)
+            .ok_or(eas_cli::Error::InvalidArg("id"))?
+            .to_string(),
+        Command::BatchId { counter } => hex::encode(ids::batch_id(counter)),
+        Command::BatchTree { leaves } => batch_tree(&leaves)?,
+        Command::SignAuth {
+            secret,
+            network_passphrase,
+            contract,
+            nonce,
+            expiration_ledger,
+            call,
+        } => auth::sign_auth(
+            &secret,
+            &network_passphrase,
+            &parse_address(&contract)?,
+            &call,
+            nonce,
+            expiration_ledger,
+        )?
+        .to_xdr_base64(Limits::none())?,
+        Command::BuildTx {
+            source,
+            seq,
+            fee,
+            contract,
+            auth,
+            call,
+        } => {
+            let auth = auth
+                .iter()
+                .map(|a| SorobanAuthorizationEntry::from_xdr_base64(a, Limits::none()))
+                .collect::<Result<Vec<_>, _>>()?;
+            tx::unsigned_tx(&source, seq, fee, &parse_address(&contract)?, &call, auth)?
+                .to_xdr_base64(Limits::none())?
+        }
+        Command::DecodeEvent { topic, value } => {
+            serde_json::to_string_pretty(&decode::event_to_json(&topic, &value)?).unwrap()
+        }
+        Command::DecodeScval { value } => serde_json::to_string_pretty(&decode::scval_to_json(
+            &decode::scval_from_base64(&value)?,
+        ))
+        .unwrap(),
+    })
+}
+
+fn batch_tree(leaves: &str) -> eas_cli::Result<String> {
+    let bad = || eas_cli::Error::InvalidArg("leaves");
+    let input: Vec<serde_json::Value> = serde_json::from_str(leaves).map_err(|_| bad())?;
+    let leaves = input
+        .iter()
+        .map(|v| {
+            Ok(batch::Leaf {
+                subject: parse_address(v["subject"].as_str().ok_or_else(bad)?)?,
+                data_hash: parse_hex32(v["data_hash"].as_str().ok_or_else(bad)?)?,
+            })
+        })
+        .collect::<eas_cli::Result<Vec<_>>>()?;
+    let tree = batch::Tree::build(&leaves)?;
+    let out: Vec<serde_json::Value> = input
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            serde_json::json!({
+                "index": i,
+                "subject": v["subject"],
+                "data_hash": v["data_hash"],
+                "leaf": hex::encode(tree.leaf(i)),
+                "proof": tree.proof(i).iter().map(hex::encode).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "root": hex::encode(tree.root()),
+        "count": leaves.len(),
+        "leaves": out,
+    }))
+    .unwrap())
+}
@@ -0,0 +1,47 @@
+//! Unsigned transaction envelopes for a single contract call.
+//!
+//! The result has no Soroban resource data (footprint, fees): that needs a
+//! simulation, so it must go through `stellar tx simulate` (or the RPC
+//! `simulateTransaction`) on an online machine before it is signed.
+
+use soroban_sdk::xdr::{
+    HostFunction, InvokeHostFunctionOp, Memo, MuxedAccount, Operation, OperationBody,
+    Preconditions, ScAddress, SequenceNumber, SorobanAuthorizationEntry, Transaction,
+    TransactionEnvelope, TransactionExt, TransactionV1Envelope, Uint256,
+};
+
+use crate::call::Call;
+use crate::{Error, Result};
+
+pub fn unsigned_tx(
+    source: &str,
+    seq_num: i64,
+    fee: u32,
+    contract: &ScAddress,
+    call: &Call,
+    auth: Vec<SorobanAuthorizationEntry>,
+) -> Result<TransactionEnvelope> {
+    let source = stellar_strkey::ed25519::PublicKey::from_string(source)
+        .map_err(|_| Error::InvalidArg("source"))?;
+
+    let op = Operation {
+        source_account: None,
+        body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function: HostFunction::InvokeContract(call.invoke_args(contract)),
+            auth: auth.try_into().map_err(|_| Error::InvalidArg("auth"))?,
+        }),
+    };
+
+    Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256(source.0)),
+            fee,
+            seq_num: SequenceNumber(seq_num),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![op].try_into().unwrap(),
+            ext: TransactionExt::V0,
+        },
+        signatures: Default::default(),
+    }))
+}
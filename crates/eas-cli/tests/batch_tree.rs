@@ -0,0 +1,61 @@
+//! Trees built by `batch-tree` are anchored and checked by the real contract.
+
+use eas_cli::batch::{Leaf, Tree};
+use eas_cli::parse_address;
+use eas_soroban::{BatchLeaf, EasContract, EasContractClient};
+use soroban_sdk::testutils::{Address as _, EnvTestConfig, Ledger as _};
+use soroban_sdk::{Address, BytesN, Env, Vec};
+
+#[test]
+fn contract_accepts_cli_proofs() {
+    let mut env = Env::default();
+    env.set_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(10);
+    let client = EasContractClient::new(&env, &env.register(EasContract, ()));
+
+    let creator = Address::generate(&env);
+    let schema_id = client.create_schema(
+        &creator,
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &true,
+        &false,
+        &0u32,
+    );
+
+    // Five leaves, so the tree needs padding on two levels.
+    let leaves: std::vec::Vec<Leaf> = (1..=5u8)
+        .map(|i| Leaf {
+            subject: parse_address(&stellar_strkey::ed25519::PublicKey([i; 32]).to_string())
+                .unwrap(),
+            data_hash: [i; 32],
+        })
+        .collect();
+    let tree = Tree::build(&leaves).unwrap();
+    let root_id = client.attest_merkle_root(
+        &creator,
+        &schema_id,
+        &BytesN::from_array(&env, &tree.root()),
+        &(leaves.len() as u32),
+        &None,
+    );
+
+    for (i, leaf) in leaves.iter().enumerate() {
+        let batch_leaf = BatchLeaf {
+            index: i as u32,
+            subject: Address::from_str(&env, &leaf.subject.to_string()),
+            data_hash: BytesN::from_array(&env, &leaf.data_hash),
+        };
+        let mut proof = Vec::new(&env);
+        for sibling in tree.proof(i) {
+            proof.push_back(BytesN::from_array(&env, &sibling));
+        }
+        assert!(client.verify_in_batch(&root_id, &batch_leaf, &proof));
+
+        let mut moved = batch_leaf.clone();
+        moved.index = (i as u32 + 1) % leaves.len() as u32;
+        assert!(!client.verify_in_batch(&root_id, &moved, &proof));
+    }
+}
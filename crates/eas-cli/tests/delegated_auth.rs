@@ -0,0 +1,250 @@
+//! Entries built by `sign-auth` are checked by the real contract: the host
+//! verifies the ed25519 signature against the network id, nonce, expiration
+//! and the exact invocation, with no auth mocking.
+
+use std::rc::Rc;
+
+use eas_cli::auth::{network_id, sign_auth};
+use eas_cli::call::Call;
+use eas_cli::{ids, tx, Error};
+use eas_soroban::{EasContract, EasContractClient};
+use ed25519_dalek::SigningKey;
+use soroban_sdk::testutils::{EnvTestConfig, Ledger as _, LedgerInfo};
+use soroban_sdk::xdr::{
+    AccountEntry, AccountEntryExt, AccountId, LedgerEntry, LedgerEntryData, LedgerEntryExt,
+    LedgerKey, LedgerKeyAccount, Limits, OperationBody, PublicKey, ReadXdr, ScAddress,
+    SequenceNumber, Thresholds, TransactionEnvelope, Uint256, WriteXdr,
+};
+use soroban_sdk::{Address, BytesN, Env};
+
+const PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+struct Key {
+    secret: String,
+    account: String,
+}
+
+fn key(seed: u8) -> Key {
+    let signing = SigningKey::from_bytes(&[seed; 32]);
+    Key {
+        secret: stellar_strkey::ed25519::PrivateKey([seed; 32])
+            .to_string()
+            .as_str()
+            .into(),
+        account: stellar_strkey::ed25519::PublicKey(signing.verifying_key().to_bytes())
+            .to_string()
+            .as_str()
+            .into(),
+    }
+}
+
+fn setup() -> (Env, Address) {
+    let mut env = Env::default();
+    env.set_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    env.ledger().set(LedgerInfo {
+        timestamp: 0,
+        protocol_version: 25,
+        sequence_number: 10,
+        network_id: network_id(PASSPHRASE),
+        base_reserve: 10,
+        min_temp_entry_ttl: 1,
+        min_persistent_entry_ttl: 1,
+        max_entry_ttl: 100,
+    });
+    let contract = env.register(EasContract, ());
+    (env, contract)
+}
+
+// Account auth checks the signer's thresholds, so the account must exist:
+// master key weight 1, all thresholds 1.
+fn create_account(env: &Env, key: &Key) {
+    let pk = stellar_strkey::ed25519::PublicKey::from_string(&key.account).unwrap();
+    let account_id = AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(pk.0)));
+    let k = Rc::new(LedgerKey::Account(LedgerKeyAccount {
+        account_id: account_id.clone(),
+    }));
+    let v = Rc::new(LedgerEntry {
+        data: LedgerEntryData::Account(AccountEntry {
+            account_id,
+            balance: 0,
+            flags: 0,
+            home_domain: Default::default(),
+            inflation_dest: None,
+            num_sub_entries: 0,
+            seq_num: SequenceNumber(0),
+            thresholds: Thresholds([1; 4]),
+            signers: Default::default(),
+            ext: AccountEntryExt::V0,
+        }),
+        last_modified_ledger_seq: 0,
+        ext: LedgerEntryExt::V0,
+    });
+    env.host().add_ledger_entry(&k, &v, None).unwrap();
+}
+
+fn address(env: &Env, strkey: &str) -> Address {
+    Address::from_str(env, strkey)
+}
+
+#[test]
+fn signed_entries_authorize_calls() {
+    let (env, contract) = setup();
+    let client = EasContractClient::new(&env, &contract);
+    let contract_sc = ScAddress::from(&contract);
+    let issuer = key(7);
+    let subject = key(8);
+    create_account(&env, &issuer);
+    let uri = "ipfs://local/schema.json";
+
+    let create = Call::CreateSchema {
+        creator: issuer.account.parse().unwrap(),
+        schema_uri: uri.into(),
+        revocable: true,
+        expires_allowed: true,
+        attester_mode: 0,
+    };
+    let entry = sign_auth(&issuer.secret, PASSPHRASE, &contract_sc, &create, 1, 20).unwrap();
+    env.set_auths(&[entry]);
+    let schema_id = client.create_schema(
+        &address(&env, &issuer.account),
+        &BytesN::from_array(&env, &ids::schema_id(uri)),
+        &true,
+        &true,
+        &0u32,
+    );
+    assert_eq!(schema_id.to_array(), ids::schema_id(uri));
+
+    let attest = Call::Attest {
+        attester: issuer.account.parse().unwrap(),
+        schema_id: ids::schema_id(uri),
+        subject: subject.account.parse().unwrap(),
+        data_hash: [4u8; 32],
+        valid_from: None,
+        expiration: Some(100),
+        nonce: 1,
+    };
+    let entry = sign_auth(&issuer.secret, PASSPHRASE, &contract_sc, &attest, 2, 20).unwrap();
+
+    // The signature covers the arguments: a different data hash is rejected.
+    env.set_auths(std::slice::from_ref(&entry));
+    let tampered = client.try_attest(
+        &address(&env, &issuer.account),
+        &schema_id,
+        &address(&env, &subject.account),
+        &BytesN::from_array(&env, &[5u8; 32]),
+        &None,
+        &Some(100),
+        &1u64,
+    );
+    assert!(tampered.is_err());
+
+    env.set_auths(&[entry]);
+    let attestation_id = client.attest(
+        &address(&env, &issuer.account),
+        &schema_id,
+        &address(&env, &subject.account),
+        &BytesN::from_array(&env, &[4u8; 32]),
+        &None,
+        &Some(100),
+        &1u64,
+    );
+    assert_eq!(attestation_id.to_array(), ids::attestation_id(1));
+
+    let revoke = Call::RevokeBy {
+        revoker: issuer.account.parse().unwrap(),
+        attestation_id: attestation_id.to_array(),
+    };
+    let entry = sign_auth(&issuer.secret, PASSPHRASE, &contract_sc, &revoke, 3, 20).unwrap();
+    env.set_auths(&[entry]);
+    client.revoke_by(&address(&env, &issuer.account), &attestation_id);
+    assert!(client.get_attestation(&attestation_id).revoked);
+}
+
+#[test]
+fn expired_or_foreign_signatures_are_rejected() {
+    let (env, contract) = setup();
+    let client = EasContractClient::new(&env, &contract);
+    let contract_sc = ScAddress::from(&contract);
+    let issuer = key(7);
+    create_account(&env, &issuer);
+    let create = Call::CreateSchema {
+        creator: issuer.account.parse().unwrap(),
+        schema_uri: "ipfs://x".into(),
+        revocable: false,
+        expires_allowed: false,
+        attester_mode: 0,
+    };
+    let call = |env: &Env| {
+        client.try_create_schema(
+            &address(env, &issuer.account),
+            &BytesN::from_array(env, &ids::schema_id("ipfs://x")),
+            &false,
+            &false,
+            &0u32,
+        )
+    };
+
+    // Ledger 10 is past the signature's expiration.
+    let stale = sign_auth(&issuer.secret, PASSPHRASE, &contract_sc, &create, 1, 9).unwrap();
+    env.set_auths(&[stale]);
+    assert!(call(&env).is_err());
+
+    let other_network = sign_auth(
+        &issuer.secret,
+        "Public Global Stellar Network ; September 2015",
+        &contract_sc,
+        &create,
+        1,
+        20,
+    )
+    .unwrap();
+    env.set_auths(&[other_network]);
+    assert!(call(&env).is_err());
+
+    // Neither attempt consumed the nonce.
+    let valid = sign_auth(&issuer.secret, PASSPHRASE, &contract_sc, &create, 1, 20).unwrap();
+    env.set_auths(&[valid]);
+    assert!(call(&env).is_ok());
+
+    assert!(matches!(
+        sign_auth(&key(9).secret, PASSPHRASE, &contract_sc, &create, 1, 20),
+        Err(Error::SignerMismatch)
+    ));
+}
+
+#[test]
+fn unsigned_tx_carries_call_and_auth() {
+    let issuer = key(7);
+    let contract: ScAddress = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE"
+        .parse()
+        .unwrap();
+    let revoke = Call::RevokeBy {
+        revoker: issuer.account.parse().unwrap(),
+        attestation_id: ids::attestation_id(1),
+    };
+    let entry = sign_auth(&issuer.secret, PASSPHRASE, &contract, &revoke, 3, 20).unwrap();
+    let envelope = tx::unsigned_tx(
+        &key(1).account,
+        42,
+        100,
+        &contract,
+        &revoke,
+        vec![entry.clone()],
+    )
+    .unwrap();
+
+    let b64 = envelope.to_xdr_base64(Limits::none()).unwrap();
+    let TransactionEnvelope::Tx(v1) =
+        TransactionEnvelope::from_xdr_base64(b64, Limits::none()).unwrap()
+    else {
+        panic!("expected a v1 envelope");
+    };
+    assert!(v1.signatures.is_empty());
+    assert_eq!(v1.tx.seq_num.0, 42);
+    let OperationBody::InvokeHostFunction(op) = &v1.tx.operations[0].body else {
+        panic!("expected invoke_host_function");
+    };
+    assert_eq!(op.auth.as_slice(), &[entry]);
+}
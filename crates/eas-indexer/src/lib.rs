@@ -0,0 +1,103 @@
+//! Off-chain indexer for the EAS contract.
+//!
+//! Events come in the shape RPC `getEvents` returns them (base64 XDR `topic`
+//! list and `value`), either live from an RPC endpoint or from exported
+//! files, and are projected into SQLite by [`store::Store`]. Decoding is done
+//! by `eas_soroban::client`, so the indexer understands both the legacy and
+//! the v2 event formats.
+
+pub mod rpc;
+pub mod server;
+pub mod store;
+
+use serde_json::Value;
+
+/// One contract event as returned by RPC `getEvents`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawEvent {
+    pub id: String,
+    pub ledger: u64,
+    pub contract_id: String,
+    pub topic: Vec<String>, // base64 XDR ScVal
+    pub value: String,      // base64 XDR ScVal
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Db(rusqlite::Error),
+    Rpc(String),
+    Decode(eas_soroban::client::DecodeError),
+    Format(&'static str),
+    /// The event stream skipped sequence numbers: some events were missed.
+    Gap {
+        expected: u64,
+        found: u64,
+    },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Db(e) => write!(f, "sqlite: {e}"),
+            Error::Rpc(e) => write!(f, "rpc: {e}"),
+            Error::Decode(e) => write!(f, "decode: {e}"),
+            Error::Format(what) => write!(f, "unexpected format: {what}"),
+            Error::Gap { expected, found } => {
+                write!(f, "event gap: expected seq {expected}, found {found}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<rusqlite::Error> for Error {
+    fn from(e: rusqlite::Error) -> Self {
+        Error::Db(e)
+    }
+}
+
+impl From<eas_soroban::client::DecodeError> for Error {
+    fn from(e: eas_soroban::client::DecodeError) -> Self {
+        Error::Decode(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl RawEvent {
+    pub fn from_json(v: &Value) -> Result<Self> {
+        let topic = v["topic"]
+            .as_array()
+            .ok_or(Error::Format("event.topic"))?
+            .iter()
+            .map(|t| t.as_str().map(str::to_owned))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(Error::Format("event.topic"))?;
+        Ok(RawEvent {
+            id: str_field(v, "id")?,
+            ledger: v["ledger"].as_u64().ok_or(Error::Format("event.ledger"))?,
+            contract_id: str_field(v, "contractId")?,
+            topic,
+            value: str_field(v, "value")?,
+        })
+    }
+}
+
+fn str_field(v: &Value, name: &'static str) -> Result<String> {
+    v[name]
+        .as_str()
+        .map(str::to_owned)
+        .ok_or(Error::Format(name))
+}
+
+/// Parses an exported event file: either a `getEvents` result object
+/// (`{"events": [...]}`) or a bare array of events.
+pub fn parse_events(text: &str) -> Result<Vec<RawEvent>> {
+    let v: Value = serde_json::from_str(text).map_err(|_| Error::Format("json"))?;
+    let events = match &v {
+        Value::Array(items) => items,
+        _ => v["events"].as_array().ok_or(Error::Format("events"))?,
+    };
+    events.iter().map(RawEvent::from_json).collect()
+}
@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+use eas_indexer::rpc::{self, Rpc};
+use eas_indexer::store::Store;
+use eas_indexer::{parse_events, server};
+
+#[derive(Parser)]
+#[command(
+    name = "eas-indexer",
+    about = "Indexes EAS contract events into SQLite"
+)]
+struct Cli {
+    /// SQLite database file.
+    #[arg(long, env = "INDEXER_DB", default_value = "eas-indexer.sqlite")]
+    db: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Follow the contract through RPC getEvents and serve queries.
+    Run {
+        #[arg(long, env = "SOROBAN_RPC_URL")]
+        rpc_url: String,
+        #[arg(long, env = "SOROBAN_CONTRACT_ID")]
+        contract_id: String,
+        /// First ledger to scan when there is no stored cursor; must reach
+        /// the contract's first event. Without it, indexing starts at the
+        /// contract's current state checkpoint.
+        #[arg(long, env = "INDEXER_START_LEDGER")]
+        start_ledger: Option<u64>,
+        #[arg(long, env = "INDEXER_POLL_MS", default_value_t = 2000)]
+        poll_ms: u64,
+        #[arg(long, default_value_t = 100)]
+        page_size: u32,
+        #[arg(long, env = "INDEXER_LISTEN", default_value = "0.0.0.0:4100")]
+        listen: String,
+    },
+    /// Ingest exported event files (getEvents results or event arrays).
+    Import { files: Vec<String> },
+    /// Only serve queries over an existing database.
+    Serve {
+        #[arg(long, env = "INDEXER_LISTEN", default_value = "0.0.0.0:4100")]
+        listen: String,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(e) = run(cli) {
+        eprintln!("eas-indexer: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    let mut store = Store::open(&cli.db)?;
+
+    match cli.command {
+        Command::Import { files } => {
+            for file in files {
+                let events = parse_events(&std::fs::read_to_string(&file)?)?;
+                let last = events.last().map(|e| e.id.clone());
+                let n = store.apply(&events, last.as_deref())?;
+                println!("{file}: {n} new events");
+            }
+        }
+        Command::Serve { listen } => {
+            server::serve(store, &listen)?;
+        }
+        Command::Run {
+            rpc_url,
+            contract_id,
+            start_ledger,
+            poll_ms,
+            page_size,
+            listen,
+        } => {
+            let served = Store::open(&cli.db)?;
+            std::thread::spawn(move || {
+                if let Err(e) = server::serve(served, &listen) {
+                    eprintln!("[indexer] server error: {e}");
+                    std::process::exit(1);
+                }
+            });
+
+            let rpc = Rpc::new(&rpc_url, &contract_id);
+            loop {
+                // Best effort: log and retry on the next tick.
+                if let Err(e) = rpc::sync(&rpc, &mut store, start_ledger, page_size) {
+                    eprintln!("[indexer] error: {e}");
+                }
+                std::thread::sleep(Duration::from_millis(poll_ms));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,191 @@
+//! Minimal Soroban JSON-RPC client: `getEvents`, `getLatestLedger` and a
+//! simulated `get_state_checkpoint` call.
+
+use serde_json::{json, Value};
+use soroban_sdk::xdr::{
+    HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Limits, Memo, MuxedAccount, Operation,
+    OperationBody, Preconditions, ReadXdr, ScAddress, ScSymbol, ScVal, SequenceNumber,
+    Transaction, TransactionEnvelope, TransactionExt, TransactionV1Envelope, Uint256, WriteXdr,
+};
+
+use crate::store::{Checkpoint, Store};
+use crate::{Error, RawEvent, Result};
+
+pub struct Rpc {
+    url: String,
+    contract_id: String,
+    agent: ureq::Agent,
+}
+
+pub struct Page {
+    pub events: Vec<RawEvent>,
+    pub cursor: Option<String>,
+    pub latest_ledger: u64,
+}
+
+impl Rpc {
+    pub fn new(url: &str, contract_id: &str) -> Self {
+        Rpc {
+            url: url.to_owned(),
+            contract_id: contract_id.to_owned(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(std::time::Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let req = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let resp: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(req)
+            .map_err(|e| Error::Rpc(e.to_string()))?
+            .into_json()
+            .map_err(|e| Error::Rpc(e.to_string()))?;
+        if let Some(err) = resp.get("error") {
+            return Err(Error::Rpc(err.to_string()));
+        }
+        resp.get("result")
+            .cloned()
+            .ok_or(Error::Format("rpc result"))
+    }
+
+    pub fn latest_ledger(&self) -> Result<u64> {
+        let result = self.call("getLatestLedger", json!({}))?;
+        result["sequence"]
+            .as_u64()
+            .ok_or(Error::Format("getLatestLedger.sequence"))
+    }
+
+    /// The contract's `get_state_checkpoint()` and the ledger it was read
+    /// at, through `simulateTransaction`. Nothing is submitted, so the
+    /// source account is a placeholder.
+    pub fn state_checkpoint(&self) -> Result<(Checkpoint, u64)> {
+        let contract_address: ScAddress = self
+            .contract_id
+            .parse()
+            .map_err(|_| Error::Format("contract id"))?;
+        let op = Operation {
+            source_account: None,
+            body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                    contract_address,
+                    function_name: ScSymbol("get_state_checkpoint".try_into().unwrap()),
+                    args: Default::default(),
+                }),
+                auth: Default::default(),
+            }),
+        };
+        let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+                fee: 100,
+                seq_num: SequenceNumber(0),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: vec![op].try_into().unwrap(),
+                ext: TransactionExt::V0,
+            },
+            signatures: Default::default(),
+        });
+        let tx = envelope
+            .to_xdr_base64(Limits::none())
+            .expect("envelope encodes");
+
+        let result = self.call("simulateTransaction", json!({ "transaction": tx }))?;
+        if let Some(err) = result["error"].as_str() {
+            return Err(Error::Rpc(err.to_owned()));
+        }
+        let xdr = result["results"][0]["xdr"]
+            .as_str()
+            .ok_or(Error::Format("simulateTransaction.results"))?;
+        let value = ScVal::from_xdr_base64(xdr, Limits::none())
+            .map_err(|_| Error::Format("get_state_checkpoint"))?;
+        state_checkpoint_from_scval(&value)
+    }
+
+    /// One page of this contract's events, continuing from `cursor` or, on
+    /// the first call, starting at `start_ledger`.
+    pub fn get_events(&self, start_ledger: u64, cursor: Option<&str>, limit: u32) -> Result<Page> {
+        let filters = json!([{ "type": "contract", "contractIds": [self.contract_id] }]);
+        let params = match cursor {
+            Some(cursor) => json!({
+                "filters": filters,
+                "pagination": { "cursor": cursor, "limit": limit },
+            }),
+            None => json!({
+                "startLedger": start_ledger,
+                "filters": filters,
+                "pagination": { "limit": limit },
+            }),
+        };
+        let result = self.call("getEvents", params)?;
+        let events = result["events"]
+            .as_array()
+            .ok_or(Error::Format("getEvents.events"))?
+            .iter()
+            .map(RawEvent::from_json)
+            .collect::<Result<Vec<_>>>()?;
+        // Older RPC versions only return a per-event pagingToken.
+        let cursor = result["cursor"]
+            .as_str()
+            .map(str::to_owned)
+            .or_else(|| events.last().map(|e| e.id.clone()));
+        Ok(Page {
+            events,
+            cursor,
+            latest_ledger: result["latestLedger"].as_u64().unwrap_or(0),
+        })
+    }
+}
+
+// `StateCheckpoint { seq, hash, ledger }` as a contracttype map.
+fn state_checkpoint_from_scval(value: &ScVal) -> Result<(Checkpoint, u64)> {
+    let bad = Error::Format("get_state_checkpoint");
+    let ScVal::Map(Some(map)) = value else {
+        return Err(bad);
+    };
+    let field = |name: &str| {
+        map.iter()
+            .find(|e| matches!(&e.key, ScVal::Symbol(s) if s.as_slice() == name.as_bytes()))
+            .map(|e| &e.val)
+    };
+    match (field("seq"), field("hash"), field("ledger")) {
+        (Some(ScVal::U64(seq)), Some(ScVal::Bytes(hash)), Some(ScVal::U64(ledger))) => {
+            let hash = hash.as_slice().try_into().map_err(|_| bad)?;
+            Ok((Checkpoint { seq: *seq, hash }, *ledger))
+        }
+        _ => Err(bad),
+    }
+}
+
+/// Pulls pages until the RPC has nothing new.
+///
+/// With no stored cursor, reading starts at `start_ledger`, which must reach
+/// back to the contract's first event (seq 1) or ingestion stops at a gap.
+/// Without one, the store is anchored at the contract's current
+/// `get_state_checkpoint()` and only later events are projected: RPC keeps a
+/// limited window of events, so older ones may not be readable at all.
+pub fn sync(rpc: &Rpc, store: &mut Store, start_ledger: Option<u64>, limit: u32) -> Result<usize> {
+    let start_ledger = match (store.cursor()?, store.start_ledger()?, start_ledger) {
+        (Some(_), _, _) => 0,
+        (None, Some(ledger), _) | (None, None, Some(ledger)) => ledger,
+        (None, None, None) => {
+            let (cp, ledger) = rpc.state_checkpoint()?;
+            store.seed(&cp, ledger)?;
+            ledger
+        }
+    };
+
+    let mut total = 0;
+    loop {
+        let cursor = store.cursor()?;
+        let page = rpc.get_events(start_ledger, cursor.as_deref(), limit)?;
+        let done = page.events.is_empty() || page.cursor == cursor;
+        total += store.apply(&page.events, page.cursor.as_deref())?;
+        if done {
+            return Ok(total);
+        }
+    }
+}
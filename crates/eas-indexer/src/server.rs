@@ -0,0 +1,73 @@
+//! Read-only HTTP query API over the SQLite projection. Routes and response
+//! shapes follow the ones `apps/api` serves from Postgres.
+
+use serde_json::{json, Value};
+
+use crate::store::Store;
+use crate::Result;
+
+/// Resolves a GET request to `(status, body)`.
+pub fn route(store: &Store, path: &str) -> Result<(u16, Value)> {
+    let path = path.split('?').next().unwrap_or_default();
+    let parts: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    let found = |v: Option<Value>, err: &str| match v {
+        Some(v) => (200, v),
+        None => (404, json!({ "error": err })),
+    };
+
+    Ok(match parts.as_slice() {
+        ["healthz"] => (200, json!({ "ok": true })),
+        ["checkpoint"] => {
+            let cp = store.checkpoint()?;
+            (
+                200,
+                json!({
+                    "seq": cp.seq,
+                    "hash": hex::encode(cp.hash),
+                    "start_seq": store.start_seq()?,
+                    "cursor": store.cursor()?,
+                }),
+            )
+        }
+        ["schemas"] => (200, json!({ "schemas": store.schemas()? })),
+        ["schemas", id] => found(store.schema(id)?, "schema_not_found"),
+        ["schemas", id, "attestations"] => (
+            200,
+            json!({ "attestations": store.schema_attestations(id)? }),
+        ),
+        ["attestations", id] => found(store.attestation(id)?, "attestation_not_found"),
+        ["batches", id] => found(store.batch(id)?, "batch_not_found"),
+        ["subjects", address, "attestations"] => (
+            200,
+            json!({ "attestations": store.subject_attestations(address)? }),
+        ),
+        _ => (404, json!({ "error": "not_found" })),
+    })
+}
+
+/// Serves [`route`] until the process exits. Give it its own `Store`
+/// (connection) so queries don't wait on ingestion.
+pub fn serve(store: Store, addr: &str) -> std::io::Result<()> {
+    let server = tiny_http::Server::http(addr).map_err(std::io::Error::other)?;
+    for req in server.incoming_requests() {
+        let (status, body) = if *req.method() != tiny_http::Method::Get {
+            (405, json!({ "error": "method_not_allowed" }))
+        } else {
+            route(&store, req.url()).unwrap_or_else(|e| {
+                eprintln!("[indexer] query error: {e}");
+                (500, json!({ "error": "internal" }))
+            })
+        };
+        let resp = tiny_http::Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(
+                "Content-Type: application/json"
+                    .parse::<tiny_http::Header>()
+                    .unwrap(),
+            );
+        // The client may have gone away; nothing to do about it.
+        let _ = req.respond(resp);
+    }
+    Ok(())
+}
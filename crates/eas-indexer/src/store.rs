@@ -0,0 +1,687 @@
+//! SQLite projection of the contract's event stream.
+//!
+//! Every event is folded into the same rolling hash the contract keeps
+//! (`sha256(prev || xdr([topics, data]))`) and its sequence number is checked,
+//! so a gap or a tampered event stops ingestion instead of silently
+//! producing a wrong view. The contract takes one `seq` per operation, shared
+//! by its legacy tuple and its v2 event; re-delivered events (a lower `seq`,
+//! or a format already folded at the current one) are skipped, which makes
+//! re-importing a file or re-reading a page harmless.
+
+use eas_interface::{STATUS_DEPRECATED, STATUS_RETIRED};
+use eas_soroban::client::{self, EasEvent};
+use rusqlite::{params, Connection, OptionalExtension, Row, Transaction};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use soroban_sdk::xdr::{Limits, ReadXdr, ScVal, ScVec, WriteXdr};
+
+use crate::{Error, RawEvent, Result};
+
+const MIGRATION_SQL: &str = "
+CREATE TABLE IF NOT EXISTS indexer_state (
+  key TEXT PRIMARY KEY,
+  value TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS schemas (
+  schema_id TEXT PRIMARY KEY,
+  creator TEXT NOT NULL,
+  schema_uri_hash TEXT NOT NULL,
+  revocable INTEGER NOT NULL,
+  expires_allowed INTEGER NOT NULL,
+  attester_mode INTEGER NOT NULL,
+  created_ledger INTEGER NOT NULL,
+  requires_subject_consent INTEGER NOT NULL DEFAULT 0,
+  status TEXT NOT NULL DEFAULT 'active',
+  successor_schema_id TEXT,
+  metadata TEXT
+);
+
+-- consent: not_required, pending, accepted or rejected; NULL when the schema
+-- was created before the store's start_seq.
+CREATE TABLE IF NOT EXISTS attestations (
+  attestation_id TEXT PRIMARY KEY,
+  schema_id TEXT NOT NULL,
+  attester TEXT NOT NULL,
+  subject TEXT NOT NULL,
+  data_hash TEXT NOT NULL,
+  timestamp INTEGER NOT NULL,
+  expiration INTEGER,
+  status_index INTEGER,
+  consent TEXT,
+  suspended INTEGER NOT NULL DEFAULT 0,
+  renounced INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS revocations (
+  attestation_id TEXT PRIMARY KEY,
+  revoker TEXT NOT NULL,
+  ledger INTEGER NOT NULL
+);
+
+-- Bits flipped in an attester's status list (revoke_status_indices).
+CREATE TABLE IF NOT EXISTS status_revocations (
+  attester TEXT NOT NULL,
+  status_index INTEGER NOT NULL,
+  ledger INTEGER NOT NULL,
+  PRIMARY KEY (attester, status_index)
+);
+
+CREATE TABLE IF NOT EXISTS batches (
+  root_id TEXT PRIMARY KEY,
+  schema_id TEXT NOT NULL,
+  attester TEXT NOT NULL,
+  root TEXT NOT NULL,
+  count INTEGER NOT NULL,
+  timestamp INTEGER NOT NULL,
+  expiration INTEGER,
+  revoked_ledger INTEGER
+);
+
+CREATE TABLE IF NOT EXISTS batch_leaf_revocations (
+  root_id TEXT NOT NULL,
+  leaf_index INTEGER NOT NULL,
+  ledger INTEGER NOT NULL,
+  PRIMARY KEY (root_id, leaf_index)
+);
+
+CREATE INDEX IF NOT EXISTS attestations_subject_idx ON attestations(subject);
+CREATE INDEX IF NOT EXISTS attestations_schema_idx ON attestations(schema_id);
+";
+
+// An attestation is revoked individually (`revocations`) or through its bit
+// in the attester's status list (`status_revocations`).
+const ATTESTATION_SELECT: &str = "
+SELECT a.*,
+       COALESCE(r.revoker, CASE WHEN s.ledger IS NOT NULL THEN a.attester END) AS revoked_by,
+       COALESCE(r.ledger, s.ledger) AS revoked_ledger
+FROM attestations a
+LEFT JOIN revocations r ON r.attestation_id = a.attestation_id
+LEFT JOIN status_revocations s
+  ON s.attester = a.attester AND s.status_index = a.status_index
+";
+
+pub struct Store {
+    conn: Connection,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub seq: u64,
+    pub hash: [u8; 32],
+}
+
+impl Store {
+    pub fn open(path: &str) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        // The query server reads through its own connection while we write.
+        conn.execute_batch("PRAGMA journal_mode = WAL;")?;
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+        Self::init(conn)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(MIGRATION_SQL)?;
+        Ok(Store { conn })
+    }
+
+    /// Applies a page of events and, in the same transaction, moves the
+    /// cursor. Returns how many events were new.
+    pub fn apply(&mut self, events: &[RawEvent], cursor: Option<&str>) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let mut cp = load_checkpoint(&tx)?;
+        // Formats already folded at `cp.seq`.
+        let mut at_seq: Vec<String> = get_state(&tx, "state_seq_formats")?
+            .map(|s| s.split(',').map(str::to_owned).collect())
+            .unwrap_or_default();
+        let mut applied = 0;
+
+        for raw in events {
+            let topics = raw
+                .topic
+                .iter()
+                .map(|t| scval(t))
+                .collect::<Result<Vec<_>>>()?;
+            let value = scval(&raw.value)?;
+
+            let format = match value {
+                ScVal::Map(_) => "v2",
+                _ => "legacy",
+            };
+            match client::event_seq(&topics, &value) {
+                Some(seq) if seq < cp.seq => continue,
+                // The other copy of the last operation, or a re-delivery.
+                Some(seq) if seq == cp.seq && at_seq.iter().any(|f| f == format) => continue,
+                Some(seq) if seq == cp.seq => {}
+                Some(seq) if seq != cp.seq + 1 => {
+                    return Err(Error::Gap {
+                        expected: cp.seq + 1,
+                        found: seq,
+                    })
+                }
+                _ => {
+                    cp.seq += 1;
+                    at_seq.clear();
+                }
+            }
+            at_seq.push(format.to_owned());
+            cp.hash = fold(&cp.hash, &topics, &value);
+
+            if let Some(decoded) = client::decode(&topics, &value)? {
+                project(&tx, &decoded.event)?;
+            }
+            applied += 1;
+        }
+
+        set_state(&tx, "state_seq", &cp.seq.to_string())?;
+        set_state(&tx, "state_seq_formats", &at_seq.join(","))?;
+        set_state(&tx, "state_hash", &hex::encode(cp.hash))?;
+        if let Some(cursor) = cursor {
+            set_state(&tx, "events_cursor", cursor)?;
+        }
+        tx.commit()?;
+        Ok(applied)
+    }
+
+    /// Anchors an empty store at a checkpoint read from the contract, so
+    /// ingestion can start at `ledger` instead of at the first event. Events
+    /// up to and at `cp.seq` are then skipped and never projected. No-op if the
+    /// store already holds a checkpoint.
+    pub fn seed(&mut self, cp: &Checkpoint, ledger: u64) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        if get_state(&tx, "state_seq")?.is_none() {
+            set_state(&tx, "state_seq", &cp.seq.to_string())?;
+            // The checkpoint covers every event of that operation.
+            set_state(&tx, "state_seq_formats", "legacy,v2")?;
+            set_state(&tx, "state_hash", &hex::encode(cp.hash))?;
+            set_state(&tx, "start_seq", &cp.seq.to_string())?;
+            set_state(&tx, "start_ledger", &ledger.to_string())?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Ledger the store was seeded at, if it was.
+    pub fn start_ledger(&self) -> Result<Option<u64>> {
+        get_state(&self.conn, "start_ledger")?
+            .map(|s| s.parse().map_err(|_| Error::Format("start_ledger")))
+            .transpose()
+    }
+
+    /// Last event seq before the projection starts: 0 unless seeded.
+    pub fn start_seq(&self) -> Result<u64> {
+        get_state(&self.conn, "start_seq")?
+            .map_or(Ok(0), |s| s.parse().map_err(|_| Error::Format("start_seq")))
+    }
+
+    pub fn cursor(&self) -> Result<Option<String>> {
+        get_state(&self.conn, "events_cursor")
+    }
+
+    pub fn checkpoint(&self) -> Result<Checkpoint> {
+        load_checkpoint(&self.conn)
+    }
+
+    pub fn schemas(&self) -> Result<Vec<Value>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT * FROM schemas ORDER BY created_ledger DESC, schema_id")?;
+        let rows = stmt.query_map([], schema_row)?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn schema(&self, schema_id: &str) -> Result<Option<Value>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT * FROM schemas WHERE schema_id = ?1",
+                [schema_id],
+                schema_row,
+            )
+            .optional()?)
+    }
+
+    pub fn attestation(&self, attestation_id: &str) -> Result<Option<Value>> {
+        let sql = format!("{ATTESTATION_SELECT} WHERE a.attestation_id = ?1");
+        Ok(self
+            .conn
+            .query_row(&sql, [attestation_id], attestation_row)
+            .optional()?)
+    }
+
+    pub fn subject_attestations(&self, subject: &str) -> Result<Vec<Value>> {
+        self.attestations_where("a.subject = ?1", subject)
+    }
+
+    pub fn schema_attestations(&self, schema_id: &str) -> Result<Vec<Value>> {
+        self.attestations_where("a.schema_id = ?1", schema_id)
+    }
+
+    pub fn batch(&self, root_id: &str) -> Result<Option<Value>> {
+        let Some(mut batch) = self
+            .conn
+            .query_row(
+                "SELECT * FROM batches WHERE root_id = ?1",
+                [root_id],
+                batch_row,
+            )
+            .optional()?
+        else {
+            return Ok(None);
+        };
+        let mut stmt = self.conn.prepare(
+            "SELECT leaf_index FROM batch_leaf_revocations WHERE root_id = ?1 ORDER BY leaf_index",
+        )?;
+        let leaves = stmt
+            .query_map([root_id], |r| r.get::<_, u32>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        batch["revoked_leaves"] = json!(leaves);
+        Ok(Some(batch))
+    }
+
+    fn attestations_where(&self, cond: &str, arg: &str) -> Result<Vec<Value>> {
+        let sql = format!(
+            "{ATTESTATION_SELECT} WHERE {cond} ORDER BY a.timestamp DESC, a.attestation_id"
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map([arg], attestation_row)?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+}
+
+fn scval(b64: &str) -> Result<ScVal> {
+    ScVal::from_xdr_base64(b64, Limits::none()).map_err(|_| Error::Format("xdr"))
+}
+
+fn fold(prev: &[u8; 32], topics: &[ScVal], value: &ScVal) -> [u8; 32] {
+    let topics = ScVal::Vec(Some(ScVec(topics.to_vec().try_into().unwrap())));
+    let payload = ScVal::Vec(Some(ScVec(vec![topics, value.clone()].try_into().unwrap())));
+    let mut h = Sha256::new();
+    h.update(prev);
+    h.update(payload.to_xdr(Limits::none()).expect("scval encodes"));
+    h.finalize().into()
+}
+
+fn project(tx: &Transaction, event: &EasEvent) -> Result<()> {
+    match event {
+        EasEvent::SchemaCreated(e) => {
+            tx.execute(
+                "INSERT OR IGNORE INTO schemas(schema_id, creator, schema_uri_hash, revocable, expires_allowed, attester_mode, created_ledger)
+                 VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    hex::encode(e.schema_id),
+                    e.creator,
+                    hex::encode(e.schema_uri_hash),
+                    e.revocable,
+                    e.expires_allowed,
+                    e.attester_mode,
+                    e.ledger as i64,
+                ],
+            )?;
+        }
+        EasEvent::SchemaFlagUpdated(e) if e.flag == "requires_subject_consent" => {
+            tx.execute(
+                "UPDATE schemas SET requires_subject_consent = ?2 WHERE schema_id = ?1",
+                params![hex::encode(e.schema_id), e.value],
+            )?;
+        }
+        EasEvent::SchemaFlagUpdated(_) => {}
+        EasEvent::SchemaStatusChanged(e) => {
+            let status = match e.status {
+                STATUS_DEPRECATED => "deprecated",
+                STATUS_RETIRED => "retired",
+                _ => "active",
+            };
+            tx.execute(
+                "UPDATE schemas SET status = ?2, successor_schema_id = ?3 WHERE schema_id = ?1",
+                params![
+                    hex::encode(e.schema_id),
+                    status,
+                    e.successor_schema_id.map(hex::encode)
+                ],
+            )?;
+        }
+        EasEvent::SchemaMetadataUpdated(e) => {
+            let metadata = e.metadata.as_ref().map(|m| {
+                json!({
+                    "name": m.name,
+                    "description": m.description,
+                    "version": { "major": m.version.0, "minor": m.version.1, "patch": m.version.2 },
+                    "tags": m.tags,
+                })
+                .to_string()
+            });
+            tx.execute(
+                "UPDATE schemas SET metadata = ?2 WHERE schema_id = ?1",
+                params![hex::encode(e.schema_id), metadata],
+            )?;
+        }
+        // Only the v2 copy carries `status_index`; whichever comes second
+        // fills it in.
+        EasEvent::Attested(e) => {
+            tx.execute(
+                "INSERT INTO attestations(attestation_id, schema_id, attester, subject, data_hash, timestamp, expiration, status_index, consent)
+                 VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8,
+                        (SELECT CASE requires_subject_consent WHEN 1 THEN 'pending' ELSE 'not_required' END
+                         FROM schemas WHERE schema_id = ?2))
+                 ON CONFLICT(attestation_id) DO UPDATE
+                 SET status_index = COALESCE(attestations.status_index, excluded.status_index)",
+                params![
+                    hex::encode(e.attestation_id),
+                    hex::encode(e.schema_id),
+                    e.attester,
+                    e.subject,
+                    hex::encode(e.data_hash),
+                    e.timestamp as i64,
+                    e.expiration.map(|x| x as i64),
+                    e.status_index,
+                ],
+            )?;
+        }
+        EasEvent::Revoked(e) => {
+            tx.execute(
+                "INSERT OR IGNORE INTO revocations(attestation_id, revoker, ledger) VALUES(?1, ?2, ?3)",
+                params![hex::encode(e.attestation_id), e.revoker, e.ledger as i64],
+            )?;
+        }
+        EasEvent::Renewed(e) => {
+            tx.execute(
+                "UPDATE attestations SET expiration = ?2 WHERE attestation_id = ?1",
+                params![hex::encode(e.attestation_id), e.new_expiration as i64],
+            )?;
+        }
+        EasEvent::Suspended(e) | EasEvent::Reinstated(e) => {
+            tx.execute(
+                "UPDATE attestations SET suspended = ?2 WHERE attestation_id = ?1",
+                params![
+                    hex::encode(e.attestation_id),
+                    matches!(event, EasEvent::Suspended(_))
+                ],
+            )?;
+        }
+        EasEvent::Renounced(e) => {
+            tx.execute(
+                "UPDATE attestations SET renounced = 1 WHERE attestation_id = ?1",
+                [hex::encode(e.attestation_id)],
+            )?;
+        }
+        EasEvent::Accepted(e) | EasEvent::Rejected(e) => {
+            let consent = match event {
+                EasEvent::Accepted(_) => "accepted",
+                _ => "rejected",
+            };
+            tx.execute(
+                "UPDATE attestations SET consent = ?2 WHERE attestation_id = ?1",
+                params![hex::encode(e.attestation_id), consent],
+            )?;
+        }
+        // The contract keeps the first revocation ledger of each bit.
+        EasEvent::StatusListRevoked(e) => {
+            for index in &e.indices {
+                tx.execute(
+                    "INSERT OR IGNORE INTO status_revocations(attester, status_index, ledger) VALUES(?1, ?2, ?3)",
+                    params![e.attester, index, e.ledger as i64],
+                )?;
+            }
+        }
+        EasEvent::BatchAttested(e) => {
+            tx.execute(
+                "INSERT OR IGNORE INTO batches(root_id, schema_id, attester, root, count, timestamp, expiration)
+                 VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    hex::encode(e.root_id),
+                    hex::encode(e.schema_id),
+                    e.attester,
+                    hex::encode(e.root),
+                    e.count,
+                    e.timestamp as i64,
+                    e.expiration.map(|x| x as i64),
+                ],
+            )?;
+        }
+        EasEvent::BatchRevoked(e) => match &e.indices {
+            None => {
+                tx.execute(
+                    "UPDATE batches SET revoked_ledger = COALESCE(revoked_ledger, ?2) WHERE root_id = ?1",
+                    params![hex::encode(e.root_id), e.ledger as i64],
+                )?;
+            }
+            Some(indices) => {
+                for index in indices {
+                    tx.execute(
+                        "INSERT OR IGNORE INTO batch_leaf_revocations(root_id, leaf_index, ledger) VALUES(?1, ?2, ?3)",
+                        params![hex::encode(e.root_id), index, e.ledger as i64],
+                    )?;
+                }
+            }
+        },
+    }
+    Ok(())
+}
+
+fn get_state(conn: &Connection, key: &str) -> Result<Option<String>> {
+    Ok(conn
+        .query_row(
+            "SELECT value FROM indexer_state WHERE key = ?1",
+            [key],
+            |r| r.get(0),
+        )
+        .optional()?)
+}
+
+fn set_state(conn: &Connection, key: &str, value: &str) -> Result<()> {
+    conn.execute(
+        "INSERT INTO indexer_state(key, value) VALUES(?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        [key, value],
+    )?;
+    Ok(())
+}
+
+fn load_checkpoint(conn: &Connection) -> Result<Checkpoint> {
+    let seq = match get_state(conn, "state_seq")? {
+        Some(s) => s.parse().map_err(|_| Error::Format("state_seq"))?,
+        None => 0,
+    };
+    let mut hash = [0u8; 32];
+    if let Some(h) = get_state(conn, "state_hash")? {
+        hex::decode_to_slice(h, &mut hash).map_err(|_| Error::Format("state_hash"))?;
+    }
+    Ok(Checkpoint { seq, hash })
+}
+
+fn schema_row(r: &Row) -> rusqlite::Result<Value> {
+    // Written by `project` from a `json!` value, so it always parses.
+    let metadata: Option<Value> = r
+        .get::<_, Option<String>>("metadata")?
+        .map(|m| serde_json::from_str(&m).unwrap());
+    Ok(json!({
+        "schema_id": r.get::<_, String>("schema_id")?,
+        "creator": r.get::<_, String>("creator")?,
+        "schema_uri_hash": r.get::<_, String>("schema_uri_hash")?,
+        "revocable": r.get::<_, bool>("revocable")?,
+        "expires_allowed": r.get::<_, bool>("expires_allowed")?,
+        "attester_mode": r.get::<_, u32>("attester_mode")?,
+        "created_ledger": r.get::<_, i64>("created_ledger")?,
+        "requires_subject_consent": r.get::<_, bool>("requires_subject_consent")?,
+        "status": r.get::<_, String>("status")?,
+        "successor_schema_id": r.get::<_, Option<String>>("successor_schema_id")?,
+        "metadata": metadata,
+    }))
+}
+
+fn attestation_row(r: &Row) -> rusqlite::Result<Value> {
+    let revoked_by: Option<String> = r.get("revoked_by")?;
+    Ok(json!({
+        "attestation_id": r.get::<_, String>("attestation_id")?,
+        "schema_id": r.get::<_, String>("schema_id")?,
+        "attester": r.get::<_, String>("attester")?,
+        "subject": r.get::<_, String>("subject")?,
+        "data_hash": r.get::<_, String>("data_hash")?,
+        "timestamp": r.get::<_, i64>("timestamp")?,
+        "expiration": r.get::<_, Option<i64>>("expiration")?,
+        "status_index": r.get::<_, Option<u32>>("status_index")?,
+        "revoked": revoked_by.is_some(),
+        "revoked_by": revoked_by,
+        "revoked_ledger": r.get::<_, Option<i64>>("revoked_ledger")?,
+        "suspended": r.get::<_, bool>("suspended")?,
+        "renounced": r.get::<_, bool>("renounced")?,
+        "consent": r.get::<_, Option<String>>("consent")?,
+    }))
+}
+
+fn batch_row(r: &Row) -> rusqlite::Result<Value> {
+    let revoked_ledger: Option<i64> = r.get("revoked_ledger")?;
+    Ok(json!({
+        "root_id": r.get::<_, String>("root_id")?,
+        "schema_id": r.get::<_, String>("schema_id")?,
+        "attester": r.get::<_, String>("attester")?,
+        "root": r.get::<_, String>("root")?,
+        "count": r.get::<_, u32>("count")?,
+        "timestamp": r.get::<_, i64>("timestamp")?,
+        "expiration": r.get::<_, Option<i64>>("expiration")?,
+        "revoked": revoked_ledger.is_some(),
+        "revoked_ledger": revoked_ledger,
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_events;
+
+    const GOLDEN: &str = include_str!("../../../contracts/eas/testdata/events.json");
+    const STATUS_GOLDEN: &str = include_str!("../../../contracts/eas/testdata/status_events.json");
+
+    #[test]
+    fn replays_golden_stream() {
+        let events = parse_events(GOLDEN).unwrap();
+        let mut store = Store::open_in_memory().unwrap();
+        assert_eq!(store.apply(&events, Some("c1")).unwrap(), events.len());
+
+        let golden: Value = serde_json::from_str(GOLDEN).unwrap();
+        let cp = store.checkpoint().unwrap();
+        assert_eq!(cp.seq, golden["checkpoint"]["seq"].as_u64().unwrap());
+        assert_eq!(hex::encode(cp.hash), golden["checkpoint"]["hash"]);
+        assert_eq!(store.cursor().unwrap().as_deref(), Some("c1"));
+
+        // Legacy and v2 copies of each fact collapse into one row.
+        assert_eq!(store.schemas().unwrap().len(), 1);
+        let schema_id = hex::encode([3u8; 32]);
+        let atts = store.schema_attestations(&schema_id).unwrap();
+        assert_eq!(atts.len(), 1);
+        assert_eq!(atts[0]["revoked"], true);
+        assert_eq!(atts[0]["revoked_ledger"], 12);
+        assert_eq!(atts[0]["expiration"], 100);
+
+        // Re-delivery is a no-op.
+        assert_eq!(store.apply(&events, None).unwrap(), 0);
+        assert_eq!(store.checkpoint().unwrap(), cp);
+
+        // A page boundary between the two copies of one operation (seq 1).
+        let mut split = Store::open_in_memory().unwrap();
+        assert_eq!(split.apply(&events[..1], None).unwrap(), 1);
+        assert_eq!(split.apply(&events[..3], None).unwrap(), 2);
+        assert_eq!(split.apply(&events, None).unwrap(), events.len() - 3);
+        assert_eq!(split.checkpoint().unwrap(), cp);
+    }
+
+    #[test]
+    fn gap_is_rejected() {
+        let events = parse_events(GOLDEN).unwrap();
+        let mut store = Store::open_in_memory().unwrap();
+        store.apply(&events[..2], Some("c1")).unwrap();
+
+        // Both copies of the attestation (seq 2) are missing.
+        let err = store.apply(&events[4..], Some("c2")).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Gap {
+                expected: 2,
+                found: 3
+            }
+        ));
+        // Nothing from the failed page was kept.
+        assert_eq!(store.checkpoint().unwrap().seq, 1);
+        assert_eq!(store.cursor().unwrap().as_deref(), Some("c1"));
+    }
+
+    #[test]
+    fn projects_status_changes() {
+        let events = parse_events(STATUS_GOLDEN).unwrap();
+        let mut store = Store::open_in_memory().unwrap();
+        assert_eq!(store.apply(&events, None).unwrap(), events.len());
+
+        let golden: Value = serde_json::from_str(STATUS_GOLDEN).unwrap();
+        assert_eq!(
+            hex::encode(store.checkpoint().unwrap().hash),
+            golden["checkpoint"]["hash"]
+        );
+
+        // Issued in order, so the status index matches the position.
+        let schema_id = hex::encode([5u8; 32]);
+        let mut atts = store.schema_attestations(&schema_id).unwrap();
+        atts.sort_by_key(|a| a["status_index"].as_u64());
+        let [first, second, third] = atts.as_slice() else {
+            panic!("expected three attestations");
+        };
+
+        // Accepted, suspended, renewed, then reinstated.
+        assert_eq!(first["consent"], "accepted");
+        assert_eq!(first["suspended"], false);
+        assert_eq!(first["expiration"], 200);
+        assert_eq!(first["revoked"], false);
+
+        // Rejected, then revoked through the status list.
+        assert_eq!(second["consent"], "rejected");
+        assert_eq!(second["revoked"], true);
+        assert_eq!(second["revoked_by"], second["attester"]);
+        assert_eq!(second["revoked_ledger"], 13);
+
+        // Still pending consent, suspended and renounced.
+        assert_eq!(third["consent"], "pending");
+        assert_eq!(third["suspended"], true);
+        assert_eq!(third["renounced"], true);
+        assert_eq!(third["revoked"], false);
+
+        let root_id = golden["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .rev()
+            .find_map(|e| {
+                let topics: Vec<&str> = e["topic"]
+                    .as_array()?
+                    .iter()
+                    .map(|t| t.as_str().unwrap())
+                    .collect();
+                match client::decode_base64(&topics, e["value"].as_str()?).ok()?? {
+                    client::Decoded {
+                        event: EasEvent::BatchAttested(b),
+                        ..
+                    } => Some(hex::encode(b.root_id)),
+                    _ => None,
+                }
+            })
+            .unwrap();
+        let batch = store.batch(&root_id).unwrap().unwrap();
+        assert_eq!(batch["count"], 4);
+        assert_eq!(batch["revoked"], true);
+        assert_eq!(batch["revoked_ledger"], 14);
+        assert_eq!(batch["revoked_leaves"], json!([2]));
+
+        // Deprecated in favour of a successor, with metadata.
+        let schema = store.schema(&schema_id).unwrap().unwrap();
+        assert_eq!(schema["status"], "deprecated");
+        assert_eq!(schema["successor_schema_id"], hex::encode([10u8; 32]));
+        assert_eq!(schema["metadata"]["name"], "kyc");
+        assert_eq!(schema["metadata"]["tags"], json!(["identity"]));
+        let successor = store.schema(&hex::encode([10u8; 32])).unwrap().unwrap();
+        assert_eq!(successor["status"], "active");
+        assert_eq!(successor["metadata"], Value::Null);
+    }
+}
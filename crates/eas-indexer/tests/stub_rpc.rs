@@ -0,0 +1,276 @@
+//! Runs the indexer against a local JSON-RPC stub serving the events the
+//! contract's unit tests captured (contracts/eas/testdata/events.json).
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use eas_indexer::rpc::{sync, Rpc};
+use eas_indexer::server::route;
+use eas_indexer::store::Store;
+use eas_indexer::{parse_events, Error, RawEvent};
+use serde_json::{json, Value};
+use soroban_sdk::xdr::{
+    HostFunction, Limits, OperationBody, ReadXdr, ScAddress, ScMap, ScMapEntry, ScSymbol, ScVal,
+    TransactionEnvelope, WriteXdr,
+};
+
+const GOLDEN: &str = include_str!("../../../contracts/eas/testdata/events.json");
+
+fn event_json(e: &RawEvent) -> Value {
+    json!({
+        "type": "contract",
+        "id": e.id,
+        "ledger": e.ledger,
+        "contractId": e.contract_id,
+        "topic": e.topic,
+        "value": e.value,
+    })
+}
+
+/// What the stub RPC knows of the contract: every event emitted so far, of
+/// which only those at or after `retained_from` can still be read.
+struct Chain {
+    events: Vec<RawEvent>,
+    retained_from: u64,
+}
+
+fn chain(events: Vec<RawEvent>) -> Arc<Mutex<Chain>> {
+    Arc::new(Mutex::new(Chain {
+        events,
+        retained_from: 0,
+    }))
+}
+
+fn symbol(s: &str) -> ScVal {
+    ScVal::Symbol(ScSymbol(s.try_into().unwrap()))
+}
+
+// What `get_state_checkpoint()` returns after `events`, as the contract
+// encodes `StateCheckpoint` (a map with sorted keys).
+fn checkpoint_scval(events: &[RawEvent]) -> ScVal {
+    let mut replay = Store::open_in_memory().unwrap();
+    replay.apply(events, None).unwrap();
+    let cp = replay.checkpoint().unwrap();
+    let ledger = events.iter().map(|e| e.ledger).max().unwrap_or(0);
+    let entry = |key: &str, val: ScVal| ScMapEntry {
+        key: symbol(key),
+        val,
+    };
+    ScVal::Map(Some(ScMap(
+        vec![
+            entry("hash", ScVal::Bytes(cp.hash.to_vec().try_into().unwrap())),
+            entry("ledger", ScVal::U64(ledger)),
+            entry("seq", ScVal::U64(cp.seq)),
+        ]
+        .try_into()
+        .unwrap(),
+    )))
+}
+
+// getEvents semantics we rely on: startLedger or cursor, limit, and a
+// response cursor pointing at the last returned event. simulateTransaction
+// only answers `get_state_checkpoint`.
+fn stub_rpc(chain: Arc<Mutex<Chain>>, contract_id: String) -> String {
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+
+    thread::spawn(move || {
+        for mut req in server.incoming_requests() {
+            let mut body = String::new();
+            req.as_reader().read_to_string(&mut body).unwrap();
+            let call: Value = serde_json::from_str(&body).unwrap();
+            let p = &call["params"];
+
+            let chain = chain.lock().unwrap();
+            let latest = chain.events.iter().map(|e| e.ledger).max().unwrap_or(0);
+            let events: Vec<&RawEvent> = chain
+                .events
+                .iter()
+                .filter(|e| e.ledger >= chain.retained_from)
+                .collect();
+
+            let result = match call["method"].as_str().unwrap() {
+                "getLatestLedger" => json!({ "sequence": latest }),
+                "simulateTransaction" => {
+                    let tx = p["transaction"].as_str().unwrap();
+                    let TransactionEnvelope::Tx(env) =
+                        TransactionEnvelope::from_xdr_base64(tx, Limits::none()).unwrap()
+                    else {
+                        panic!("expected a v1 envelope");
+                    };
+                    let OperationBody::InvokeHostFunction(op) = &env.tx.operations[0].body else {
+                        panic!("expected a contract call");
+                    };
+                    let HostFunction::InvokeContract(args) = &op.host_function else {
+                        panic!("expected a contract call");
+                    };
+                    assert_eq!(
+                        args.contract_address,
+                        contract_id.parse::<ScAddress>().unwrap()
+                    );
+                    assert_eq!(args.function_name.0.as_slice(), b"get_state_checkpoint");
+                    let value = checkpoint_scval(&chain.events);
+                    json!({
+                        "results": [{ "xdr": value.to_xdr_base64(Limits::none()).unwrap() }],
+                        "latestLedger": latest,
+                    })
+                }
+                "getEvents" => {
+                    assert_eq!(p["filters"][0]["contractIds"][0], contract_id);
+                    let limit = p["pagination"]["limit"].as_u64().unwrap() as usize;
+                    let page: Vec<&RawEvent> = match p["pagination"]["cursor"].as_str() {
+                        Some(c) => events.into_iter().filter(|e| e.id.as_str() > c).collect(),
+                        None => {
+                            let start = p["startLedger"].as_u64().unwrap();
+                            events.into_iter().filter(|e| e.ledger >= start).collect()
+                        }
+                    };
+                    let page: Vec<&RawEvent> = page.into_iter().take(limit).collect();
+                    let cursor = page
+                        .last()
+                        .map(|e| e.id.clone())
+                        .or_else(|| p["pagination"]["cursor"].as_str().map(str::to_owned));
+                    json!({
+                        "events": page.iter().map(|e| event_json(e)).collect::<Vec<_>>(),
+                        "latestLedger": latest,
+                        "cursor": cursor,
+                    })
+                }
+                m => panic!("unexpected method {m}"),
+            };
+            let resp = json!({ "jsonrpc": "2.0", "id": call["id"], "result": result });
+            req.respond(tiny_http::Response::from_string(resp.to_string()))
+                .unwrap();
+        }
+    });
+    url
+}
+
+#[test]
+fn indexes_from_stub_rpc() {
+    let events = parse_events(GOLDEN).unwrap();
+    let contract_id = events[0].contract_id.clone();
+    let url = stub_rpc(chain(events.clone()), contract_id.clone());
+    let rpc = Rpc::new(&url, &contract_id);
+
+    // Small pages to exercise the cursor.
+    let mut store = Store::open_in_memory().unwrap();
+    assert_eq!(sync(&rpc, &mut store, Some(1), 4).unwrap(), events.len());
+    assert_eq!(
+        store.cursor().unwrap().as_deref(),
+        Some(events.last().unwrap().id.as_str())
+    );
+    // Caught up: nothing new on the next poll.
+    assert_eq!(sync(&rpc, &mut store, None, 4).unwrap(), 0);
+
+    let golden: Value = serde_json::from_str(GOLDEN).unwrap();
+    let (status, cp) = route(&store, "/checkpoint").unwrap();
+    assert_eq!(status, 200);
+    assert_eq!(cp["seq"], golden["checkpoint"]["seq"]);
+    assert_eq!(cp["hash"], golden["checkpoint"]["hash"]);
+
+    let schema_id = hex::encode([3u8; 32]);
+    let (status, schema) = route(&store, &format!("/schemas/{schema_id}")).unwrap();
+    assert_eq!(status, 200);
+    assert_eq!(schema["attester_mode"], 1);
+    assert_eq!(schema["created_ledger"], 10);
+
+    let (_, list) = route(&store, &format!("/schemas/{schema_id}/attestations")).unwrap();
+    let att = &list["attestations"][0];
+    assert_eq!(att["data_hash"], hex::encode([4u8; 32]));
+    assert_eq!(att["revoked"], true);
+    assert_eq!(att["revoked_by"], schema["creator"]);
+
+    let subject = att["subject"].as_str().unwrap();
+    let (_, by_subject) = route(&store, &format!("/subjects/{subject}/attestations")).unwrap();
+    assert_eq!(by_subject["attestations"].as_array().unwrap().len(), 1);
+
+    let id = att["attestation_id"].as_str().unwrap();
+    let (status, one) = route(&store, &format!("/attestations/{id}")).unwrap();
+    assert_eq!(status, 200);
+    assert_eq!(&one, att);
+
+    let (status, missing) = route(&store, "/attestations/00").unwrap();
+    assert_eq!(status, 404);
+    assert_eq!(missing["error"], "attestation_not_found");
+}
+
+#[test]
+fn file_import_matches_rpc_ingest() {
+    let events = parse_events(GOLDEN).unwrap();
+    let contract_id = events[0].contract_id.clone();
+    let rpc = Rpc::new(
+        &stub_rpc(chain(events.clone()), contract_id.clone()),
+        &contract_id,
+    );
+
+    let mut from_rpc = Store::open_in_memory().unwrap();
+    sync(&rpc, &mut from_rpc, Some(1), 100).unwrap();
+
+    let mut from_file = Store::open_in_memory().unwrap();
+    from_file.apply(&events, None).unwrap();
+
+    assert_eq!(
+        from_rpc.checkpoint().unwrap(),
+        from_file.checkpoint().unwrap()
+    );
+    for path in ["/schemas", "/checkpoint"] {
+        let (_, a) = route(&from_rpc, path).unwrap();
+        let (_, b) = route(&from_file, path).unwrap();
+        if path == "/checkpoint" {
+            assert_eq!(a["hash"], b["hash"]);
+        } else {
+            assert_eq!(a, b);
+        }
+    }
+}
+
+#[test]
+fn starts_mid_stream_from_contract_checkpoint() {
+    let events = parse_events(GOLDEN).unwrap();
+    let contract_id = events[0].contract_id.clone();
+    let at = |ledger| -> Vec<RawEvent> {
+        events
+            .iter()
+            .filter(|e| e.ledger <= ledger)
+            .cloned()
+            .collect()
+    };
+
+    // The RPC has already dropped ledger 10 (schema creation) when the
+    // indexer first starts, at ledger 11.
+    let live = chain(at(11));
+    live.lock().unwrap().retained_from = 11;
+    let rpc = Rpc::new(&stub_rpc(live.clone(), contract_id.clone()), &contract_id);
+
+    // An explicit start ledger must reach seq 1.
+    let mut strict = Store::open_in_memory().unwrap();
+    let err = sync(&rpc, &mut strict, Some(11), 100).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Gap {
+            expected: 1,
+            found: 2
+        }
+    ));
+
+    // Without one, the store is anchored at the contract's checkpoint and
+    // what it already covers is skipped.
+    let mut store = Store::open_in_memory().unwrap();
+    assert_eq!(sync(&rpc, &mut store, None, 100).unwrap(), 0);
+    assert_eq!(store.checkpoint().unwrap().seq, 2);
+    assert_eq!(store.start_ledger().unwrap(), Some(11));
+
+    live.lock().unwrap().events = at(12);
+    assert_eq!(sync(&rpc, &mut store, None, 100).unwrap(), 2);
+
+    let golden: Value = serde_json::from_str(GOLDEN).unwrap();
+    let (_, cp) = route(&store, "/checkpoint").unwrap();
+    assert_eq!(cp["seq"], golden["checkpoint"]["seq"]);
+    assert_eq!(cp["hash"], golden["checkpoint"]["hash"]);
+    assert_eq!(cp["start_seq"], 2);
+
+    // Facts from before the anchor are not in the projection.
+    let (status, _) = route(&store, &format!("/schemas/{}", hex::encode([3u8; 32]))).unwrap();
+    assert_eq!(status, 404);
+}
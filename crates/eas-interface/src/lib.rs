@@ -0,0 +1,268 @@
+//! Read-only interface of the EAS contract for other Soroban contracts.
+//!
+//! The types here are the ones the contract itself uses (`eas_soroban`
+//! re-exports them) and the contract implements [`EasInterface`], so a
+//! signature change on either side fails to compile instead of failing at
+//! call time. Typical use from a consumer contract:
+//!
+//! ```ignore
+//! let eas = eas_interface::EasClient::new(&env, &eas_contract_id);
+//! let ok = eas.verify(&attestation_id).is_some_and(|r| r.valid);
+//! ```
+
+#![no_std]
+
+use soroban_sdk::{contractclient, contracttype, Address, Bytes, BytesN, Env, Vec};
+
+pub const MODE_PERMISSIONLESS: u32 = 0;
+pub const MODE_ISSUER_ONLY: u32 = 1;
+
+pub const STATUS_ACTIVE: u32 = 0;
+pub const STATUS_DEPRECATED: u32 = 1;
+pub const STATUS_RETIRED: u32 = 2;
+
+pub const CONSENT_NOT_REQUIRED: u32 = 0;
+pub const CONSENT_PENDING: u32 = 1;
+pub const CONSENT_ACCEPTED: u32 = 2;
+pub const CONSENT_REJECTED: u32 = 3;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Schema {
+    pub schema_uri_hash: BytesN<32>,
+    pub creator: Address,
+    pub revocable: bool,
+    pub expires_allowed: bool,
+    pub attester_mode: u32, // 0=permissionless, 1=issuer_only
+    pub renewable: bool,
+    pub min_validity: Option<u64>, // ledgers
+    pub max_validity: Option<u64>, // ledgers
+    pub expiration_required: bool,
+    pub status: u32, // 0=active, 1=deprecated, 2=retired
+    pub successor_schema_id: Option<BytesN<32>>,
+    pub requires_subject_consent: bool,
+    pub subject_can_renounce: bool,
+    pub salted_commitments: bool, // data_hash = sha256(salt || claim)
+    pub merkle_claims: bool,      // data_hash = Merkle root over claim fields
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attestation {
+    pub schema_id: BytesN<32>,
+    pub attester: Address,
+    pub subject: Address,
+    pub data_hash: BytesN<32>,
+    pub timestamp: u64,          // ledger sequence
+    pub valid_from: Option<u64>, // ledger sequence
+    pub expiration: Option<u64>, // ledger sequence
+    pub revoked: bool,
+    pub pending_signatures: bool,
+    pub consent_status: u32, // 0=not_required, 1=pending, 2=accepted, 3=rejected
+    pub renounced: bool,
+    pub status_index: Option<u32>, // bit in the attester's status list
+    pub suspended: bool,
+}
+
+// One record anchoring a whole batch of (subject, data_hash) leaves.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchAttestation {
+    pub schema_id: BytesN<32>,
+    pub attester: Address,
+    pub root: BytesN<32>,
+    pub count: u32,
+    pub timestamp: u64,          // ledger sequence
+    pub expiration: Option<u64>, // ledger sequence
+    pub revoked: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchLeaf {
+    pub index: u32,
+    pub subject: Address,
+    pub data_hash: BytesN<32>,
+}
+
+// First time the contract saw an off-chain attestation hash (or its revocation).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimestampRecord {
+    pub ledger: u64,
+    pub time: u64, // ledger close time, unix seconds
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifyResult {
+    pub exists: bool,
+    pub valid: bool,
+    pub revoked: bool,
+    pub renounced: bool,
+    pub suspended: bool,
+    pub expired: bool,
+    pub not_yet_valid: bool,
+    pub schema_status: u32,
+    pub consent_status: u32,
+    pub pending_signatures: bool,
+    pub signers: Vec<Address>,
+    pub schema_id: BytesN<32>,
+    pub attester: Address,
+    pub subject: Address,
+    pub data_hash: BytesN<32>,
+    pub timestamp: u64,
+    pub valid_from: Option<u64>,
+    pub expiration: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Renewal {
+    pub ledger: u64,
+    pub previous_expiration: u64,
+    pub new_expiration: u64,
+}
+
+// Acceptance conditions on top of `verify`'s own validity. Unset fields (and
+// an empty `attesters`) accept anything; ages are in ledgers.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifyPolicy {
+    pub schema_id: Option<BytesN<32>>,
+    pub attesters: Vec<Address>,
+    pub max_age: Option<u64>,                // ledgers since `timestamp`
+    pub min_remaining_validity: Option<u64>, // ledgers left before `expiration`
+    pub subject: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PolicyFailure {
+    NotFound,
+    Revoked,
+    Renounced,
+    Suspended,
+    Expired,
+    NotYetValid,
+    ConsentPending,
+    ConsentRejected,
+    PendingSignatures,
+    SchemaRetired,
+    WrongSchema,
+    UntrustedAttester,
+    TooOld,
+    ExpiresTooSoon,
+    WrongSubject,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PolicyResult {
+    pub valid: bool, // no failures
+    pub failures: Vec<PolicyFailure>,
+    pub ledger: u64, // ledger the policy was evaluated at
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StatusKind {
+    Issued, // past its cosignature threshold, for threshold attestations
+    Revoked,
+    Suspended,
+    Reinstated,
+    Renewed,
+    Renounced,
+    Accepted,
+    Rejected,
+}
+
+// One entry of an attestation's status history, in ledger order. Renewal
+// details stay in `get_renewals`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatusChange {
+    pub kind: StatusKind,
+    pub ledger: u64,
+}
+
+// `verify` as it would have answered at the close of `ledger`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HistoricalStatus {
+    pub ledger: u64,
+    pub exists: bool, // recorded at or before `ledger`
+    pub valid: bool,
+    pub revoked: bool,
+    pub renounced: bool,
+    pub suspended: bool,
+    pub expired: bool,
+    pub not_yet_valid: bool,
+    pub schema_retired: bool,
+    pub consent_status: u32,
+    pub pending_signatures: bool,
+    pub expiration: Option<u64>, // in force at `ledger`
+}
+
+/// Queries a consumer needs to accept or reject an attestation. Functions
+/// that panic in the contract (unknown ids) make the generated client
+/// panic too; use the `try_` variants to handle them.
+#[contractclient(name = "EasClient")]
+pub trait EasInterface {
+    /// `None` if the attestation does not exist.
+    fn verify(env: Env, attestation_id: BytesN<32>) -> Option<VerifyResult>;
+
+    /// Every failed condition, base validity included, in a fixed order.
+    fn verify_with_policy(
+        env: Env,
+        attestation_id: BytesN<32>,
+        policy: VerifyPolicy,
+    ) -> PolicyResult;
+
+    /// Validity at a past (or the current) ledger, rebuilt from the status
+    /// history; `None` if the attestation does not exist.
+    fn verify_at(env: Env, attestation_id: BytesN<32>, ledger: u64) -> Option<HistoricalStatus>;
+
+    fn get_status_history(env: Env, attestation_id: BytesN<32>) -> Vec<StatusChange>;
+
+    fn get_schema(env: Env, schema_id: BytesN<32>) -> Schema;
+
+    fn get_attestation(env: Env, attestation_id: BytesN<32>) -> Attestation;
+
+    fn get_renewals(env: Env, attestation_id: BytesN<32>) -> Vec<Renewal>;
+
+    /// Leaf membership and validity in a batch anchored by `attest_merkle_root`.
+    fn verify_in_batch(
+        env: Env,
+        root_id: BytesN<32>,
+        leaf: BatchLeaf,
+        proof: Vec<BytesN<32>>,
+    ) -> bool;
+
+    fn get_batch(env: Env, root_id: BytesN<32>) -> BatchAttestation;
+
+    /// Disclosed claim against a salted commitment; does not check validity.
+    fn verify_claim(
+        env: Env,
+        attestation_id: BytesN<32>,
+        preimage: Bytes,
+        salt: BytesN<32>,
+    ) -> bool;
+
+    /// Disclosed Merkle field (the field bytes, not its leaf hash) against
+    /// `data_hash`; does not check validity.
+    fn verify_field(
+        env: Env,
+        attestation_id: BytesN<32>,
+        field: Bytes,
+        proof: Vec<BytesN<32>>,
+    ) -> bool;
+
+    fn get_timestamp(env: Env, data: BytesN<32>) -> Option<TimestampRecord>;
+
+    fn get_offchain_revocation(
+        env: Env,
+        signer: Address,
+        data: BytesN<32>,
+    ) -> Option<TimestampRecord>;
+}
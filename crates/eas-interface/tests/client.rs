@@ -0,0 +1,57 @@
+//! Calls a registered EAS contract through the generated `EasClient`, the
+//! way a consumer contract would, and checks it sees the same records as
+//! the contract's own client.
+
+use eas_interface::{EasClient, CONSENT_NOT_REQUIRED, STATUS_ACTIVE};
+use eas_soroban::{EasContract, EasContractClient};
+use soroban_sdk::testutils::{Address as _, EnvTestConfig, Ledger as _};
+use soroban_sdk::{Address, BytesN, Env};
+
+#[test]
+fn interface_client_reads_the_contract() {
+    let mut env = Env::default();
+    env.set_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(10);
+    let contract_id = env.register(EasContract, ());
+    let contract = EasContractClient::new(&env, &contract_id);
+    let eas = EasClient::new(&env, &contract_id);
+
+    let creator = Address::generate(&env);
+    let subject = Address::generate(&env);
+    let schema_id = BytesN::from_array(&env, &[1u8; 32]);
+    contract.create_schema(&creator, &schema_id, &true, &true, &0u32);
+    let data_hash = BytesN::from_array(&env, &[2u8; 32]);
+    let id = contract.attest(
+        &creator,
+        &schema_id,
+        &subject,
+        &data_hash,
+        &None,
+        &Some(20u64),
+        &1u64,
+    );
+
+    let result = eas.verify(&id).unwrap();
+    assert!(result.valid);
+    assert_eq!(result.schema_status, STATUS_ACTIVE);
+    assert_eq!(result.consent_status, CONSENT_NOT_REQUIRED);
+    assert_eq!(Some(result), contract.verify(&id));
+
+    assert_eq!(eas.get_schema(&schema_id), contract.get_schema(&schema_id));
+    assert_eq!(eas.get_attestation(&id).data_hash, data_hash);
+    assert!(eas.get_renewals(&id).is_empty());
+
+    env.ledger().set_sequence_number(20);
+    assert!(eas.verify(&id).unwrap().expired);
+
+    let unknown = BytesN::from_array(&env, &[9u8; 32]);
+    assert_eq!(eas.verify(&unknown), None);
+    assert!(eas.try_get_schema(&unknown).is_err());
+
+    assert_eq!(eas.get_timestamp(&data_hash), None);
+    let record = contract.timestamp(&data_hash);
+    assert_eq!(eas.get_timestamp(&data_hash), Some(record));
+}